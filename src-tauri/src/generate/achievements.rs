@@ -1,99 +1,113 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
 use crate::{
-    structure::handbook::{category::Category, gi::achievement::Achievements, Language},
+    structure::handbook::{
+        category::Category,
+        gi::achievement::{Achievement, Achievements},
+        Language,
+    },
     utility::{TextMap, TextMapError},
 };
 
-use super::{commands::generate_command, commands::CommandMap, output_log, ResultData};
+use super::{
+    commands::generate_command, commands::CommandMap, output_log, ResultAccumulator, ResultData,
+    TextHashes,
+};
 
 #[derive(Serialize)]
 pub struct AchievementResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub commands: CommandMap,
     pub category: Category,
 }
 
-pub(crate) fn generate_achievements<F>(
+impl TextHashes for Achievement {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [self.title_text_map_hash, self.desc_text_map_hash].into_iter()
+    }
+}
+
+pub(crate) fn read_achievements<F>(
     app_handle: &tauri::AppHandle,
     resources: &String,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     read_excel_bin_output: F,
-) -> Result<(), String>
+) -> Result<Achievements, String>
 where
     F: Fn(&str, &str) -> Result<Achievements, TextMapError>,
 {
-    let achievements: Achievements =
-        match read_excel_bin_output(&resources.to_string(), "AchievementExcelConfigData") {
-            Ok(data) => data,
-            Err(e) => {
-                let error_msg = format!("Failed to read Achievements: {}", e);
-                output_log(app_handle, "error", &error_msg);
-                return Err(error_msg);
-            }
-        };
-    let mut total_achievements = 0;
-    for achievement in achievements.iter() {
-        total_achievements += 1;
+    match read_excel_bin_output(&resources.to_string(), "AchievementExcelConfigData") {
+        Ok(data) => Ok(data),
+        Err(e) => {
+            let error_msg = format!("Failed to read Achievements: {}", e);
+            output_log(app_handle, "error", &error_msg);
+            Err(error_msg)
+        }
+    }
+}
 
-        let name = text_map
-            .get(&achievement.title_text_map_hash.to_string())
-            .cloned();
-        let desc = text_map
-            .get(&achievement.desc_text_map_hash.to_string())
-            .cloned();
+pub(crate) fn generate_achievements(
+    app_handle: &tauri::AppHandle,
+    achievements: &[Achievement],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+) -> Result<(), String> {
+    let mut entries: ResultAccumulator<AchievementResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_achievements = 0;
+        for achievement in achievements.iter() {
+            total_achievements += 1;
 
-        let command = generate_command(
-            Category::Achievements,
-            achievement.id as u32,
-            "/am grant",
-            super::commands::GameType::GenshinImpact,
-        );
+            let name = text_map
+                .get(&achievement.title_text_map_hash.to_string())
+                .cloned();
+            let desc = text_map
+                .get(&achievement.desc_text_map_hash.to_string())
+                .cloned();
 
-        let achievement_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Achievements(a) if a.id == achievement.id))
-            .and_then(|r| {
-                if let ResultData::Achievements(a) = r {
-                    Some(a)
-                } else {
-                    None
-                }
-            });
+            let command = generate_command(
+                Category::Achievements,
+                achievement.id as u32,
+                "/am grant",
+                super::commands::GameType::GenshinImpact,
+            );
 
-        if let Some(existing_achievement) = achievement_result {
-            existing_achievement
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_achievement
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
+            let achievement_result = entries.get_mut(achievement.id);
 
-            result.push(ResultData::Achievements(AchievementResult {
-                id: achievement.id,
-                name: names,
-                description: descriptions,
-                category: Category::Achievements,
-                commands: command,
-            }))
+            if let Some(existing_achievement) = achievement_result {
+                existing_achievement
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_achievement
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+
+                entries.insert(
+                    achievement.id,
+                    AchievementResult {
+                        id: achievement.id,
+                        name: names,
+                        description: descriptions,
+                        category: Category::Achievements,
+                        commands: command,
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Achievements added: {}", total_achievements),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Achievements added: {}", total_achievements),
-    );
-    drop(achievements);
+    result.extend(entries.into_results(ResultData::Achievements));
     Ok(())
 }
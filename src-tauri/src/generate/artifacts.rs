@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
@@ -10,21 +10,22 @@ use crate::{
 };
 
 use super::{
-    commands::generate_command, commands::CommandMap, output_log, GameExcelReader, ResultData,
+    commands::generate_command, commands::CommandMap, output_log, GameExcelReader,
+    ResultAccumulator, ResultData, TextHashes,
 };
 
 #[derive(Serialize)]
 pub struct ArtifactResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub image: String,
     pub category: Category,
     pub rarity: i64,
     pub commands: CommandMap,
 }
 
-struct ArtifactData {
+pub(crate) struct ArtifactData {
     id: i64,
     name: i64,
     description: i64,
@@ -55,7 +56,7 @@ impl ArtifactData {
 
     fn from_star_rail(star_rail_relic: RelicElement) -> Self {
         let command = generate_command(
-            Category::Artifacts,
+            Category::Relics,
             star_rail_relic.id as u32,
             "/give",
             super::commands::GameType::HonkaiStarRail,
@@ -74,19 +75,18 @@ impl ArtifactData {
     }
 }
 
-pub(crate) fn generate_artifacts<G>(
+impl TextHashes for ArtifactData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [self.name, self.description].into_iter()
+    }
+}
+
+pub(crate) fn read_artifacts(
     app_handle: &tauri::AppHandle,
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-    get_image: G,
-) -> Result<(), String>
-where
-    G: Fn(&str, &str, &str) -> String,
-{
-    let artifacts: Vec<ArtifactData> = match excel_reader {
+) -> Result<Vec<ArtifactData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             match excel_reader.read_excel_data::<Artifact>(resources, "ReliquaryExcelConfigData") {
                 Ok(data) => data.into_iter().map(ArtifactData::from_genshin).collect(),
@@ -107,57 +107,65 @@ where
                 }
             }
         }
-    };
+    })
+}
 
-    let mut total_artifacts = 0;
-    for artifact in artifacts.iter() {
-        total_artifacts += 1;
+pub(crate) fn generate_artifacts<G>(
+    app_handle: &tauri::AppHandle,
+    artifacts: &[ArtifactData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+    get_image: G,
+) -> Result<(), String>
+where
+    G: Fn(&str, &str, &str) -> String,
+{
+    let mut entries: ResultAccumulator<ArtifactResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_artifacts = 0;
+        for artifact in artifacts.iter() {
+            total_artifacts += 1;
 
-        let name = text_map.get(&artifact.name.to_string()).cloned();
-        let desc = text_map.get(&artifact.description.to_string()).cloned();
+            let name = text_map.get(&artifact.name.to_string()).cloned();
+            let desc = text_map.get(&artifact.description.to_string()).cloned();
 
-        let image = get_image("genshin-impact", &artifact.icon, "artifacts");
+            let image = get_image("genshin-impact", &artifact.icon, "artifacts");
 
-        let artifact_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Artifacts(a) if a.id == artifact.id))
-            .and_then(|r| {
-                if let ResultData::Artifacts(a) = r {
-                    Some(a)
-                } else {
-                    None
-                }
-            });
+            let artifact_result = entries.get_mut(artifact.id);
 
-        if let Some(existing_artifact) = artifact_result {
-            existing_artifact
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_artifact
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
-            result.push(ResultData::Artifacts(ArtifactResult {
-                id: artifact.id,
-                name: names,
-                description: descriptions,
-                rarity: artifact.rarity.parse::<i64>().unwrap_or(0),
-                image,
-                category: artifact.category.clone(),
-                commands: artifact.commands.clone(),
-            }))
+            if let Some(existing_artifact) = artifact_result {
+                existing_artifact
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_artifact
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+                entries.insert(
+                    artifact.id,
+                    ArtifactResult {
+                        id: artifact.id,
+                        name: names,
+                        description: descriptions,
+                        rarity: artifact.rarity.parse::<i64>().unwrap_or(0),
+                        image,
+                        category: artifact.category.clone(),
+                        commands: artifact.commands.clone(),
+                    },
+                )
+            }
         }
-    }
 
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Artifacts added: {}", total_artifacts),
-    );
-    drop(artifacts);
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Artifacts added: {}", total_artifacts),
+        );
+    }
+    result.extend(entries.into_results(ResultData::Artifacts));
     Ok(())
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
@@ -11,10 +11,10 @@ use crate::{
 
 use super::{
     commands::{generate_command, CommandMap},
-    output_log, GameExcelReader, ResultData,
+    output_log, GameExcelReader, ResultAccumulator, ResultData, TextHashes,
 };
 
-struct CharacterData {
+pub(crate) struct CharacterData {
     id: i64,
     name: i64,
     description: Option<i64>,
@@ -45,7 +45,7 @@ impl CharacterData {
 
     fn from_star_rail(star_rail_char: CharacterListSR) -> Self {
         let command = generate_command(
-            Category::Characters,
+            Category::Avatars,
             star_rail_char.avatar_id as u32,
             "/give",
             super::commands::GameType::HonkaiStarRail,
@@ -58,18 +58,24 @@ impl CharacterData {
             icon: star_rail_char
                 .default_avatar_head_icon_path
                 .replace("SpriteOutput/AvatarIcon/Avatar/", ""),
-            category: Category::Characters,
+            category: Category::Avatars,
             commands: command,
         }
     }
 }
 
+impl TextHashes for CharacterData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [self.name, self.description.unwrap_or_default()].into_iter()
+    }
+}
+
 #[derive(Serialize)]
 pub struct CharacterResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<HashMap<Language, String>>,
+    pub description: Option<BTreeMap<Language, String>>,
     pub image: String,
     pub category: Category,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,19 +83,12 @@ pub struct CharacterResult {
     pub commands: CommandMap,
 }
 
-pub(crate) fn generate_character<G>(
+pub(crate) fn read_characters(
     app_handle: &tauri::AppHandle,
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-    get_image: G,
-) -> Result<(), String>
-where
-    G: Fn(&str, &str, &str) -> String,
-{
-    let characters: Vec<CharacterData> = match excel_reader {
+) -> Result<Vec<CharacterData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             match excel_reader.read_excel_data::<CharactersGI>(resources, "AvatarExcelConfigData") {
                 Ok(data) => data.into_iter().map(CharacterData::from_genshin).collect(),
@@ -113,70 +112,78 @@ where
                 }
             }
         }
-    };
-    let mut total_characters = 0;
-    for character in characters.iter() {
-        if character.id == 10000001 || character.id > 11000000 {
-            continue;
-        }
-        total_characters += 1;
-        let name = text_map.get(&character.name.to_string()).cloned();
-        if name.is_none() {
-            continue;
-        }
-        let desc = text_map
-            .get(&character.description.unwrap_or_default().to_string())
-            .cloned()
-            .unwrap_or_default();
+    })
+}
 
-        let image = get_image("genshin-impact", &character.icon, "characters");
+pub(crate) fn generate_character<G>(
+    app_handle: &tauri::AppHandle,
+    characters: &[CharacterData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+    get_image: G,
+) -> Result<(), String>
+where
+    G: Fn(&str, &str, &str) -> String,
+{
+    let mut entries: ResultAccumulator<CharacterResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_characters = 0;
+        for character in characters.iter() {
+            if character.id == 10000001 || character.id > 11000000 {
+                continue;
+            }
+            total_characters += 1;
+            let name = text_map.get(&character.name.to_string()).cloned();
+            if name.is_none() {
+                continue;
+            }
+            let desc = text_map
+                .get(&character.description.unwrap_or_default().to_string())
+                .cloned()
+                .unwrap_or_default();
 
-        let character_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Characters(c) if c.id == character.id))
-            .and_then(|r| {
-                if let ResultData::Characters(c) = r {
-                    Some(c)
-                } else {
-                    None
-                }
-            });
+            let image = get_image("genshin-impact", &character.icon, "characters");
+
+            let character_result = entries.get_mut(character.id);
 
-        if let Some(existing_character) = character_result {
-            existing_character
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            if !desc.is_empty() {
+            if let Some(existing_character) = character_result {
                 existing_character
-                    .description
-                    .clone()
-                    .unwrap()
-                    .insert(lang.clone(), desc);
-            }
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            if !desc.is_empty() {
-                descriptions.insert(lang.clone(), desc);
-            }
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                if !desc.is_empty() {
+                    existing_character
+                        .description
+                        .get_or_insert_with(Default::default)
+                        .insert(lang.clone(), desc);
+                }
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                if !desc.is_empty() {
+                    descriptions.insert(lang.clone(), desc);
+                }
 
-            result.push(ResultData::Characters(CharacterResult {
-                id: character.id,
-                name: names,
-                description: Some(descriptions),
-                rarity: Some(character.rarity.parse::<u8>().unwrap_or_default()),
-                image,
-                category: character.category.clone(),
-                commands: character.commands.clone(),
-            }))
+                entries.insert(
+                    character.id,
+                    CharacterResult {
+                        id: character.id,
+                        name: names,
+                        description: Some(descriptions),
+                        rarity: Some(character.rarity.parse::<u8>().unwrap_or_default()),
+                        image,
+                        category: character.category.clone(),
+                        commands: character.commands.clone(),
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Characters added: {}", total_characters),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Characters added: {}", total_characters),
-    );
-    drop(characters);
+    result.extend(entries.into_results(ResultData::Characters));
     Ok(())
 }
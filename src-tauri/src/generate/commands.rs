@@ -144,7 +144,7 @@ fn generate_gio_commands(category: &Category, id: u32) -> BTreeMap<String, Comma
 
 fn generate_hsr_commands(category: &Category, id: u32) -> BTreeMap<String, Commands> {
     let commands = match category {
-        Category::Characters => vec![
+        Category::Characters | Category::Avatars => vec![
             ("Normal", format!("/give {}", id)),
             ("With Level", format!("/give {} lv<level>", id)),
             ("With Skill Level", format!("/give {} sl<SkillLevel>", id)),
@@ -171,6 +171,10 @@ fn generate_hsr_commands(category: &Category, id: u32) -> BTreeMap<String, Comma
                 format!("/give {} lv<level> si<superimposition> x<amount>", id),
             ),
         ],
+        Category::Items => vec![
+            ("Normal", format!("/give {}", id)),
+            ("With Amount", format!("/give {} x<amount>", id)),
+        ],
         Category::Monsters => vec![
             ("Normal", format!("/spawn {}", id)),
             ("With Amount", format!("/spawn {} x<amount>", id)),
@@ -1,102 +1,112 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
 use crate::{
-    structure::handbook::{category::Category, gi::dungeons::Dungeons, Language},
+    structure::handbook::{
+        category::Category,
+        gi::dungeons::{Dungeon, Dungeons},
+        Language,
+    },
     utility::{TextMap, TextMapError},
 };
 
 use super::{
     commands::{generate_command, CommandMap},
-    output_log, ResultData,
+    output_log, ResultAccumulator, ResultData, TextHashes,
 };
 
 #[derive(Serialize)]
 pub struct DungeonsResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub category: Category,
     pub commands: CommandMap,
 }
 
-pub fn generate_dungeons<F>(
+impl TextHashes for Dungeon {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [self.name_text_map_hash, self.desc_text_map_hash].into_iter()
+    }
+}
+
+pub fn read_dungeons<F>(
     app_handle: &tauri::AppHandle,
     resources: &String,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     read_excel_bin_output: F,
-) -> Result<(), String>
+) -> Result<Dungeons, String>
 where
     F: Fn(&str, &str) -> Result<Dungeons, TextMapError>,
 {
-    let dungeons: Dungeons =
-        match read_excel_bin_output(&resources.to_string(), "DungeonExcelConfigData") {
-            Ok(data) => data,
-            Err(e) => {
-                let error_msg = format!("Failed to read Dungeons: {}", e);
-                output_log(app_handle, "error", &error_msg);
-                return Err(error_msg);
-            }
-        };
+    match read_excel_bin_output(&resources.to_string(), "DungeonExcelConfigData") {
+        Ok(data) => Ok(data),
+        Err(e) => {
+            let error_msg = format!("Failed to read Dungeons: {}", e);
+            output_log(app_handle, "error", &error_msg);
+            Err(error_msg)
+        }
+    }
+}
 
-    let mut total_dungeons = 0;
-    for dungeon in dungeons.iter() {
-        total_dungeons += 1;
+pub fn generate_dungeons(
+    app_handle: &tauri::AppHandle,
+    dungeons: &[Dungeon],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+) -> Result<(), String> {
+    let mut entries: ResultAccumulator<DungeonsResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_dungeons = 0;
+        for dungeon in dungeons.iter() {
+            total_dungeons += 1;
 
-        let name = text_map
-            .get(&dungeon.name_text_map_hash.to_string())
-            .cloned();
-        let desc = text_map
-            .get(&dungeon.desc_text_map_hash.to_string())
-            .cloned();
+            let name = text_map
+                .get(&dungeon.name_text_map_hash.to_string())
+                .cloned();
+            let desc = text_map
+                .get(&dungeon.desc_text_map_hash.to_string())
+                .cloned();
 
-        let command = generate_command(
-            Category::Dungeons,
-            dungeon.id as u32,
-            "/tp",
-            super::commands::GameType::GenshinImpact,
-        );
+            let command = generate_command(
+                Category::Dungeons,
+                dungeon.id as u32,
+                "/tp",
+                super::commands::GameType::GenshinImpact,
+            );
 
-        let dungeon_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Dungeons(d) if d.id == dungeon.id))
-            .and_then(|r| {
-                if let ResultData::Dungeons(d) = r {
-                    Some(d)
-                } else {
-                    None
-                }
-            });
+            let dungeon_result = entries.get_mut(dungeon.id);
 
-        if let Some(existing_dungeon) = dungeon_result {
-            existing_dungeon
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_dungeon
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
-            result.push(ResultData::Dungeons(DungeonsResult {
-                id: dungeon.id,
-                name: names,
-                description: descriptions,
-                category: Category::Dungeons,
-                commands: command,
-            }))
+            if let Some(existing_dungeon) = dungeon_result {
+                existing_dungeon
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_dungeon
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+                entries.insert(
+                    dungeon.id,
+                    DungeonsResult {
+                        id: dungeon.id,
+                        name: names,
+                        description: descriptions,
+                        category: Category::Dungeons,
+                        commands: command,
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Dungeons added: {}", total_dungeons),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Dungeons added: {}", total_dungeons),
-    );
-    drop(dungeons);
+    result.extend(entries.into_results(ResultData::Dungeons));
     Ok(())
 }
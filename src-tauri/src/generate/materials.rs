@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
@@ -8,13 +8,13 @@ use crate::structure::handbook::{gi::materials::Material, sr::items::Items};
 use crate::utility::TextMap;
 
 use super::commands::{generate_command, CommandMap};
-use super::{output_log, GameExcelReader, ResultData};
+use super::{output_log, GameExcelReader, ResultAccumulator, ResultData, TextHashes};
 
 #[derive(Serialize)]
 pub struct MaterialsResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub image: String,
     pub category: Category,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -22,7 +22,7 @@ pub struct MaterialsResult {
     pub commands: CommandMap,
 }
 
-struct MaterialData {
+pub(crate) struct MaterialData {
     rarity: Option<i64>,
     id: i64,
     name: i64,
@@ -53,7 +53,7 @@ impl MaterialData {
 
     fn from_star_rail(star_rail_mat: Items) -> Self {
         let command = generate_command(
-            Category::Materials,
+            Category::Items,
             star_rail_mat.id as u32,
             "/give",
             super::commands::GameType::HonkaiStarRail,
@@ -72,19 +72,18 @@ impl MaterialData {
     }
 }
 
-pub(crate) fn generate_materials<G>(
+impl TextHashes for MaterialData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [self.name, self.description].into_iter()
+    }
+}
+
+pub(crate) fn read_materials(
     app_handle: &tauri::AppHandle,
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-    get_image: G,
-) -> Result<(), String>
-where
-    G: Fn(&str, &str, &str) -> String,
-{
-    let materials: Vec<MaterialData> = match excel_reader {
+) -> Result<Vec<MaterialData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             match excel_reader.read_excel_data::<Material>(resources, "MaterialExcelConfigData") {
                 Ok(data) => data.into_iter().map(MaterialData::from_genshin).collect(),
@@ -105,55 +104,64 @@ where
                 }
             }
         }
-    };
-    let mut total_materials = 0;
-    for material in materials.iter() {
-        let name = text_map.get(&material.name.to_string()).cloned();
-        let desc = text_map.get(&material.description.to_string()).cloned();
+    })
+}
 
-        total_materials += 1;
+pub(crate) fn generate_materials<G>(
+    app_handle: &tauri::AppHandle,
+    materials: &[MaterialData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+    get_image: G,
+) -> Result<(), String>
+where
+    G: Fn(&str, &str, &str) -> String,
+{
+    let mut entries: ResultAccumulator<MaterialsResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_materials = 0;
+        for material in materials.iter() {
+            let name = text_map.get(&material.name.to_string()).cloned();
+            let desc = text_map.get(&material.description.to_string()).cloned();
 
-        let image = get_image("genshin-impact", &material.icon, "materials");
+            total_materials += 1;
 
-        let material_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Materials(m) if m.id == material.id))
-            .and_then(|r| {
-                if let ResultData::Materials(m) = r {
-                    Some(m)
-                } else {
-                    None
-                }
-            });
+            let image = get_image("genshin-impact", &material.icon, "materials");
 
-        if let Some(existing_material) = material_result {
-            existing_material
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_material
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
-            result.push(ResultData::Materials(MaterialsResult {
-                id: material.id,
-                name: names,
-                description: descriptions,
-                rarity: material.rarity,
-                image,
-                category: material.category.clone(),
-                commands: material.commands.clone(),
-            }))
+            let material_result = entries.get_mut(material.id);
+
+            if let Some(existing_material) = material_result {
+                existing_material
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_material
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+                entries.insert(
+                    material.id,
+                    MaterialsResult {
+                        id: material.id,
+                        name: names,
+                        description: descriptions,
+                        rarity: material.rarity,
+                        image,
+                        category: material.category.clone(),
+                        commands: material.commands.clone(),
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Materials added: {}", total_materials),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Materials added: {}", total_materials),
-    );
-    drop(materials);
+    result.extend(entries.into_results(ResultData::Materials));
     Ok(())
 }
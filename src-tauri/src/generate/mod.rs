@@ -12,6 +12,7 @@ pub mod scenes;
 pub mod weapons;
 
 use std::{
+    collections::{HashMap, HashSet},
     fmt::{self, Formatter},
     fs,
     path::Path,
@@ -19,24 +20,31 @@ use std::{
 };
 
 use log::{info, warn};
+use rayon::prelude::*;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tauri::Emitter;
 
 use crate::{
-    structure::handbook::Language,
-    utility::{format_file_size, read_excel_bin_output, read_text_map, TextMapError},
+    structure::handbook::{
+        category::Category,
+        gi::{achievement::Achievements, dungeons::Dungeons},
+        Language,
+    },
+    utility::{
+        format_file_size, read_excel_bin_output, read_text_map_filtered, TextMap, TextMapError,
+    },
 };
 
 use self::{
-    achievements::{generate_achievements, AchievementResult},
-    artifacts::{generate_artifacts, ArtifactResult},
-    characters::{generate_character, CharacterResult},
-    dungeons::{generate_dungeons, DungeonsResult},
-    materials::{generate_materials, MaterialsResult},
-    monsters::{generate_monsters, MonstersResult},
-    quests::{generate_quests, MainQuestResult},
+    achievements::{generate_achievements, read_achievements, AchievementResult},
+    artifacts::{generate_artifacts, read_artifacts, ArtifactData, ArtifactResult},
+    characters::{generate_character, read_characters, CharacterData, CharacterResult},
+    dungeons::{generate_dungeons, read_dungeons, DungeonsResult},
+    materials::{generate_materials, read_materials, MaterialData, MaterialsResult},
+    monsters::{generate_monsters, read_monsters, MonsterData, MonstersResult},
+    quests::{generate_quests, read_quests, MainQuestResult, MissionData},
     scenes::{generate_scenes, ScenesResult},
-    weapons::{generate_weapons, WeaponResult},
+    weapons::{generate_weapons, read_weapons, WeaponResult, WeaponsData},
 };
 
 #[derive(Serialize)]
@@ -53,6 +61,194 @@ pub(crate) enum ResultData {
     Monsters(MonstersResult),
 }
 
+impl ResultData {
+    fn category(&self) -> &Category {
+        match self {
+            ResultData::Characters(result) => &result.category,
+            ResultData::Materials(result) => &result.category,
+            ResultData::Weapons(result) => &result.category,
+            ResultData::Artifacts(result) => &result.category,
+            ResultData::Achievements(result) => &result.category,
+            ResultData::Quests(result) => &result.category,
+            ResultData::Dungeons(result) => &result.category,
+            ResultData::Scenes(result) => &result.category,
+            ResultData::Monsters(result) => &result.category,
+        }
+    }
+
+    fn id(&self) -> i64 {
+        match self {
+            ResultData::Characters(result) => result.id,
+            ResultData::Materials(result) => result.id,
+            ResultData::Weapons(result) => result.id,
+            ResultData::Artifacts(result) => result.id,
+            ResultData::Achievements(result) => result.id,
+            ResultData::Quests(result) => result.id,
+            ResultData::Dungeons(result) => result.id,
+            ResultData::Scenes(result) => result.id,
+            ResultData::Monsters(result) => result.id,
+        }
+    }
+}
+
+/// Orders generated entries by category, then ID, so regenerating a
+/// handbook from the same resources writes the same file, whatever the
+/// selection order. Entries sharing both keep the order they were read in.
+pub(crate) fn sort_results(result: &mut [ResultData]) {
+    result.sort_by(|a, b| (a.category(), a.id()).cmp(&(b.category(), b.id())));
+}
+
+/// Excel rows whose texts are looked up in the TextMaps by hash.
+pub(crate) trait TextHashes {
+    fn text_hashes(&self) -> impl Iterator<Item = i64>;
+}
+
+/// Excel rows read for one selection, before any TextMap is loaded.
+enum SelectionData {
+    Characters(Vec<CharacterData>),
+    Materials(Vec<MaterialData>),
+    Weapons(Vec<WeaponsData>),
+    Artifacts(Vec<ArtifactData>),
+    Achievements(Achievements),
+    Quests(Vec<MissionData>),
+    Dungeons(Dungeons),
+    /// Scene names are not in the TextMaps, so they are read when generated.
+    Scenes,
+    Monsters(Vec<MonsterData>),
+}
+
+impl SelectionData {
+    fn read(
+        app_handle: &tauri::AppHandle,
+        selection: &SelectHandbookArgs,
+        resources: &str,
+        excel_reader: &GameExcelReader,
+    ) -> Result<Self, String> {
+        Ok(match selection {
+            SelectHandbookArgs::Characters | SelectHandbookArgs::Avatars => {
+                SelectionData::Characters(read_characters(app_handle, resources, excel_reader)?)
+            }
+            SelectHandbookArgs::Materials | SelectHandbookArgs::Items => {
+                SelectionData::Materials(read_materials(app_handle, resources, excel_reader)?)
+            }
+            SelectHandbookArgs::Weapons | SelectHandbookArgs::LightCones => {
+                SelectionData::Weapons(read_weapons(app_handle, resources, excel_reader)?)
+            }
+            SelectHandbookArgs::Artifacts | SelectHandbookArgs::Relics => {
+                SelectionData::Artifacts(read_artifacts(app_handle, resources, excel_reader)?)
+            }
+            SelectHandbookArgs::Achievements => SelectionData::Achievements(read_achievements(
+                app_handle,
+                &resources.to_string(),
+                read_excel_bin_output,
+            )?),
+            SelectHandbookArgs::Quests => {
+                SelectionData::Quests(read_quests(app_handle, resources, excel_reader)?)
+            }
+            SelectHandbookArgs::Dungeons => SelectionData::Dungeons(read_dungeons(
+                app_handle,
+                &resources.to_string(),
+                read_excel_bin_output,
+            )?),
+            SelectHandbookArgs::Scenes => SelectionData::Scenes,
+            SelectHandbookArgs::Monsters => {
+                SelectionData::Monsters(read_monsters(resources, excel_reader)?)
+            }
+        })
+    }
+
+    /// Adds the hashes of every text the rows need to `hashes`.
+    fn collect_hashes(&self, hashes: &mut HashSet<String>) {
+        fn collect<T: TextHashes>(rows: &[T], hashes: &mut HashSet<String>) {
+            for row in rows {
+                hashes.extend(row.text_hashes().map(|hash| hash.to_string()));
+            }
+        }
+        match self {
+            SelectionData::Characters(rows) => collect(rows, hashes),
+            SelectionData::Materials(rows) => collect(rows, hashes),
+            SelectionData::Weapons(rows) => collect(rows, hashes),
+            SelectionData::Artifacts(rows) => collect(rows, hashes),
+            SelectionData::Achievements(rows) => collect(rows, hashes),
+            SelectionData::Quests(rows) => collect(rows, hashes),
+            SelectionData::Dungeons(rows) => collect(rows, hashes),
+            SelectionData::Scenes => {}
+            SelectionData::Monsters(rows) => collect(rows, hashes),
+        }
+    }
+
+    fn generate(
+        &self,
+        app_handle: &tauri::AppHandle,
+        resources: &str,
+        text_maps: &[(Language, TextMap)],
+        result: &mut Vec<ResultData>,
+    ) -> Result<(), String> {
+        match self {
+            SelectionData::Characters(rows) => {
+                generate_character(app_handle, rows, text_maps, result, get_image)
+            }
+            SelectionData::Materials(rows) => {
+                generate_materials(app_handle, rows, text_maps, result, get_image)
+            }
+            SelectionData::Weapons(rows) => {
+                generate_weapons(app_handle, rows, text_maps, result, get_image)
+            }
+            SelectionData::Artifacts(rows) => {
+                generate_artifacts(app_handle, rows, text_maps, result, get_image)
+            }
+            SelectionData::Achievements(rows) => {
+                generate_achievements(app_handle, rows, text_maps, result)
+            }
+            SelectionData::Quests(rows) => generate_quests(app_handle, rows, text_maps, result),
+            SelectionData::Dungeons(rows) => generate_dungeons(app_handle, rows, text_maps, result),
+            SelectionData::Scenes => generate_scenes(
+                app_handle,
+                &resources.to_string(),
+                result,
+                read_excel_bin_output,
+            ),
+            SelectionData::Monsters(rows) => {
+                generate_monsters(app_handle, rows, text_maps, result, get_image)
+            }
+        }
+    }
+}
+
+/// Entries generated for one selection, merged by ID as every language is
+/// added, in the order they were first generated.
+pub(crate) struct ResultAccumulator<T> {
+    entries: Vec<T>,
+    positions: HashMap<i64, usize>,
+}
+
+impl<T> Default for ResultAccumulator<T> {
+    fn default() -> Self {
+        ResultAccumulator {
+            entries: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<T> ResultAccumulator<T> {
+    pub fn get_mut(&mut self, id: i64) -> Option<&mut T> {
+        let position = *self.positions.get(&id)?;
+        Some(&mut self.entries[position])
+    }
+
+    /// Adds an entry generated for the first time. An entry with the same ID
+    /// is expected to be merged through [`Self::get_mut`] instead.
+    pub fn insert(&mut self, id: i64, entry: T) {
+        self.positions.insert(id, self.entries.len());
+        self.entries.push(entry);
+    }
+
+    pub fn into_results(self, wrap: fn(T) -> ResultData) -> impl Iterator<Item = ResultData> {
+        self.entries.into_iter().map(wrap)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 struct OutputEmit {
     log_level: String,
@@ -155,6 +351,24 @@ pub enum SelectHandbookArgs {
     Dungeons,
     Scenes,
     Monsters,
+    Avatars,
+    LightCones,
+    Relics,
+    Items,
+}
+
+impl SelectHandbookArgs {
+    /// Whether the selection can be generated from the resources of `game`.
+    pub fn supports(&self, game: &GameTypeFandom) -> bool {
+        match self {
+            SelectHandbookArgs::Monsters => true,
+            SelectHandbookArgs::Avatars
+            | SelectHandbookArgs::LightCones
+            | SelectHandbookArgs::Relics
+            | SelectHandbookArgs::Items => matches!(game, GameTypeFandom::StarRail),
+            _ => matches!(game, GameTypeFandom::GenshinImpact),
+        }
+    }
 }
 
 impl fmt::Display for SelectHandbookArgs {
@@ -169,6 +383,10 @@ impl fmt::Display for SelectHandbookArgs {
             SelectHandbookArgs::Dungeons => write!(f, "dungeons"),
             SelectHandbookArgs::Scenes => write!(f, "scenes"),
             SelectHandbookArgs::Monsters => write!(f, "monsters"),
+            SelectHandbookArgs::Avatars => write!(f, "avatars"),
+            SelectHandbookArgs::LightCones => write!(f, "light-cones"),
+            SelectHandbookArgs::Relics => write!(f, "relics"),
+            SelectHandbookArgs::Items => write!(f, "items"),
         }
     }
 }
@@ -187,6 +405,10 @@ impl FromStr for SelectHandbookArgs {
             "dungeons" => Ok(SelectHandbookArgs::Dungeons),
             "scenes" => Ok(SelectHandbookArgs::Scenes),
             "monsters" => Ok(SelectHandbookArgs::Monsters),
+            "avatars" => Ok(SelectHandbookArgs::Avatars),
+            "light-cones" | "light cones" | "lightcones" => Ok(SelectHandbookArgs::LightCones),
+            "relics" => Ok(SelectHandbookArgs::Relics),
+            "items" => Ok(SelectHandbookArgs::Items),
             _ => Err(s.to_string()),
         }
     }
@@ -210,6 +432,10 @@ impl AllVariants for SelectHandbookArgs {
             SelectHandbookArgs::Dungeons,
             SelectHandbookArgs::Scenes,
             SelectHandbookArgs::Monsters,
+            SelectHandbookArgs::Avatars,
+            SelectHandbookArgs::LightCones,
+            SelectHandbookArgs::Relics,
+            SelectHandbookArgs::Items,
         ]
     }
 }
@@ -280,6 +506,42 @@ where
     }
 }
 
+/// Parses the selections of `game`, all of them when none are given. A
+/// selection of the other game is rejected rather than generated with the
+/// wrong categories and commands.
+pub(crate) fn select_for_game(
+    args: &Option<Vec<String>>,
+    game: &GameTypeFandom,
+) -> Result<Vec<SelectHandbookArgs>, String> {
+    let given = args.as_ref().is_some_and(|args| !args.is_empty());
+    let mut selections = parse_selections::<SelectHandbookArgs>(args)?;
+    if !given {
+        selections.retain(|selection| selection.supports(game));
+        return Ok(selections);
+    }
+
+    let unsupported: Vec<String> = selections
+        .iter()
+        .filter(|selection| !selection.supports(game))
+        .map(|selection| selection.to_string())
+        .collect();
+    if !unsupported.is_empty() {
+        let available = SelectHandbookArgs::all()
+            .iter()
+            .filter(|selection| selection.supports(game))
+            .map(|selection| selection.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!(
+            "Error: Selections not available for {}: {}. Available selections are: {}",
+            game,
+            unsupported.join(", "),
+            available
+        ));
+    }
+    Ok(selections)
+}
+
 // TODO: Change or remove this to use from the user input provide for the path of the images
 pub static PATH_IMAGE: &str = "./src/images/";
 pub static URL_IMAGE: &str = "https://api.elaxan.com/images/";
@@ -317,107 +579,59 @@ pub fn generate_handbook(
         }
     }
 
-    let parsed_selections = parse_selections::<SelectHandbookArgs>(&selections)?;
+    let game_type = GameTypeFandom::from_str(game)
+        .map_err(|_| "Unsupported game or game not selected".to_string())?;
+    let parsed_selections = select_for_game(&selections, &game_type)?;
     let parsed_languages = parse_selections::<Language>(&languages)?;
     let start = std::time::Instant::now();
-    let excel_reader = match game {
-        "genshin-impact" => GameExcelReader::GenshinImpact(GenshinImpactExcelReader),
-        "star-rail" => GameExcelReader::StarRail(StarRailExcelReader),
-        _ => return Err("Unsupported game or game not selected".to_string()),
+    let excel_reader = match game_type {
+        GameTypeFandom::GenshinImpact => GameExcelReader::GenshinImpact(GenshinImpactExcelReader),
+        GameTypeFandom::StarRail => GameExcelReader::StarRail(StarRailExcelReader),
     };
-    let mut result = Vec::new();
-
-    for lang in &parsed_languages {
-        output_log(
-            &app_handle,
-            "info",
-            &format!("Reading TextMap{}.json", lang.to_string().to_uppercase()),
-        );
-        let text_map =
-            read_text_map(args.text_map_path, &lang.to_string()).map_err(|e| e.to_string())?;
-
-        for selection in &parsed_selections {
-            let generate_result = match selection {
-                SelectHandbookArgs::Characters => generate_character(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                    get_image,
-                ),
-                SelectHandbookArgs::Materials => generate_materials(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                    get_image,
-                ),
-                SelectHandbookArgs::Weapons => generate_weapons(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                    get_image,
-                ),
-                SelectHandbookArgs::Artifacts => generate_artifacts(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                    get_image,
-                ),
-                SelectHandbookArgs::Achievements => generate_achievements(
-                    &app_handle,
-                    &args.excel_path.to_string(),
-                    lang,
-                    &text_map,
-                    &mut result,
-                    read_excel_bin_output,
-                ),
-                SelectHandbookArgs::Quests => generate_quests(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                ),
-                SelectHandbookArgs::Dungeons => generate_dungeons(
-                    &app_handle,
-                    &args.excel_path.to_string(),
-                    lang,
-                    &text_map,
-                    &mut result,
-                    read_excel_bin_output,
-                ),
-                SelectHandbookArgs::Scenes => generate_scenes(
-                    &app_handle,
-                    &args.excel_path.to_string(),
-                    &mut result,
-                    read_excel_bin_output,
-                ),
-                SelectHandbookArgs::Monsters => generate_monsters(
-                    &app_handle,
-                    args.excel_path,
-                    lang,
-                    &text_map,
-                    &mut result,
-                    &excel_reader,
-                    get_image,
-                ),
-            };
-            generate_result?;
-        }
+    // The Excel rows of every selection are read first, so the TextMaps can
+    // be streamed keeping only the texts these rows reference.
+    let data: Vec<SelectionData> = parsed_selections
+        .par_iter()
+        .map(|selection| {
+            SelectionData::read(&app_handle, selection, args.excel_path, &excel_reader)
+        })
+        .collect::<Result<_, _>>()?;
+    let mut hashes = HashSet::new();
+    for selection in &data {
+        selection.collect_hashes(&mut hashes);
     }
 
+    // Every TextMap is read at the same time, as they are independent files.
+    let text_maps: Vec<(Language, TextMap)> = parsed_languages
+        .par_iter()
+        .map(|lang| {
+            output_log(
+                &app_handle,
+                "info",
+                &format!("Reading TextMap{}.json", lang.to_string().to_uppercase()),
+            );
+            read_text_map_filtered(args.text_map_path, &lang.to_string(), &hashes)
+                .map(|text_map| (lang.clone(), text_map))
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<_, _>>()?;
+    drop(hashes);
+
+    // Selections are generated concurrently. Results are collected in
+    // selection order, so the output does not depend on scheduling.
+    let results: Vec<Vec<ResultData>> = data
+        .par_iter()
+        .map(|selection| {
+            let mut result = Vec::new();
+            selection
+                .generate(&app_handle, args.excel_path, &text_maps, &mut result)
+                .map(|_| result)
+        })
+        .collect::<Result<_, _>>()?;
+    drop(text_maps);
+    let mut result: Vec<ResultData> = results.into_iter().flatten().collect();
+    sort_results(&mut result);
+
     output_log(
         &app_handle,
         "info",
@@ -1,5 +1,5 @@
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use crate::{
     structure::handbook::{
@@ -11,14 +11,16 @@ use crate::{
     utility::TextMap,
 };
 
-use super::{commands::CommandMap, output_log, GameExcelReader, ResultData};
+use super::{
+    commands::CommandMap, output_log, GameExcelReader, ResultAccumulator, ResultData, TextHashes,
+};
 
 #[derive(Serialize)]
 pub struct MonstersResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<HashMap<Language, String>>,
+    pub description: Option<BTreeMap<Language, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
     pub category: Category,
@@ -26,7 +28,7 @@ pub struct MonstersResult {
     pub commands: Option<CommandMap>,
 }
 
-struct MonsterData {
+pub(crate) struct MonsterData {
     id: i64,
     name: i64,
     description: Option<i64>,
@@ -59,20 +61,17 @@ impl MonsterData {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn generate_monsters<G>(
-    app_handle: &tauri::AppHandle,
+impl TextHashes for MonsterData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [Some(self.name), self.description].into_iter().flatten()
+    }
+}
+
+pub(crate) fn read_monsters(
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-    get_image: G,
-) -> Result<(), String>
-where
-    G: Fn(&str, &str, &str) -> String,
-{
-    let monsters: Vec<MonsterData> = match excel_reader {
+) -> Result<Vec<MonsterData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             let monsters = match excel_reader
                 .read_excel_data::<GIMonster>(resources, "MonsterExcelConfigData")
@@ -105,67 +104,76 @@ where
                 };
             monsters.iter().map(MonsterData::from_star_rail).collect()
         }
-    };
+    })
+}
 
-    let mut total_monsters = 0;
-    for monster in monsters.iter() {
-        let name = text_map.get(&monster.name.to_string()).cloned();
-        if name.is_none() {
-            continue;
-        }
-        total_monsters += 1;
+pub(crate) fn generate_monsters<G>(
+    app_handle: &tauri::AppHandle,
+    monsters: &[MonsterData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+    get_image: G,
+) -> Result<(), String>
+where
+    G: Fn(&str, &str, &str) -> String,
+{
+    let mut entries: ResultAccumulator<MonstersResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_monsters = 0;
+        for monster in monsters.iter() {
+            let name = text_map.get(&monster.name.to_string()).cloned();
+            if name.is_none() {
+                continue;
+            }
+            total_monsters += 1;
 
-        let description = monster
-            .description
-            .and_then(|desc| text_map.get(&desc.to_string()).cloned());
-        let image = monster
-            .icon
-            .as_ref()
-            .map(|icon| get_image("genshin-impact", icon, "monsters"));
+            let description = monster
+                .description
+                .and_then(|desc| text_map.get(&desc.to_string()).cloned());
+            let image = monster
+                .icon
+                .as_ref()
+                .map(|icon| get_image("genshin-impact", icon, "monsters"));
 
-        let monster_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Monsters(m) if m.id == monster.id))
-            .and_then(|r| {
-                if let ResultData::Monsters(m) = r {
-                    Some(m)
-                } else {
-                    None
-                }
-            });
+            let monster_result = entries.get_mut(monster.id);
 
-        if let Some(existing_monster) = monster_result {
-            existing_monster
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            if let Some(desc) = description {
+            if let Some(existing_monster) = monster_result {
                 existing_monster
-                    .description
-                    .get_or_insert_with(HashMap::new)
-                    .insert(lang.clone(), desc);
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                if let Some(desc) = description {
+                    existing_monster
+                        .description
+                        .get_or_insert_with(BTreeMap::new)
+                        .insert(lang.clone(), desc);
+                }
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let descriptions = description.map(|desc| {
+                    let mut map = BTreeMap::new();
+                    map.insert(lang.clone(), desc);
+                    map
+                });
+                entries.insert(
+                    monster.id,
+                    MonstersResult {
+                        id: monster.id,
+                        name: names,
+                        description: descriptions,
+                        image,
+                        category: monster.category.clone(),
+                        commands: monster.commands.clone(),
+                    },
+                )
             }
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let descriptions = description.map(|desc| {
-                let mut map = HashMap::new();
-                map.insert(lang.clone(), desc);
-                map
-            });
-            result.push(ResultData::Monsters(MonstersResult {
-                id: monster.id,
-                name: names,
-                description: descriptions,
-                image,
-                category: monster.category.clone(),
-                commands: monster.commands.clone(),
-            }))
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Monsters added: {}", total_monsters),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Monsters added: {}", total_monsters),
-    );
+    result.extend(entries.into_results(ResultData::Monsters));
     Ok(())
 }
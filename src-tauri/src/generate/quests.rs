@@ -1,29 +1,27 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
 use crate::{
-    structure::handbook::{
-        category::Category, gi::main_quests::MainQuest, sr::mission::MissionElement, Language,
-    },
+    structure::handbook::{category::Category, gi::main_quests::MainQuest, Language},
     utility::TextMap,
 };
 
 use super::{
     commands::{generate_command, CommandMap},
-    output_log, GameExcelReader, ResultData,
+    output_log, GameExcelReader, ResultAccumulator, ResultData, TextHashes,
 };
 
 #[derive(Serialize)]
 pub struct MainQuestResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub category: Category,
     pub commands: CommandMap,
 }
 
-struct MissionData {
+pub(crate) struct MissionData {
     id: i64,
     name: i64,
     description: Option<i64>,
@@ -45,32 +43,20 @@ impl MissionData {
             commands,
         }
     }
+}
 
-    fn from_star_rail(star_rail_mission: MissionElement) -> Self {
-        let commands = generate_command(
-            Category::Quests,
-            star_rail_mission.main_mission_id as u32,
-            "/q",
-            super::commands::GameType::HonkaiStarRail,
-        );
-        Self {
-            id: star_rail_mission.main_mission_id,
-            name: star_rail_mission.name.hash,
-            description: None,
-            commands,
-        }
+impl TextHashes for MissionData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [Some(self.name), self.description].into_iter().flatten()
     }
 }
 
-pub(crate) fn generate_quests(
+pub(crate) fn read_quests(
     app_handle: &tauri::AppHandle,
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-) -> Result<(), String> {
-    let main_quests: Vec<MissionData> = match excel_reader {
+) -> Result<Vec<MissionData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             match excel_reader.read_excel_data::<MainQuest>(resources, "MainQuestExcelConfigData") {
                 Ok(data) => data.into_iter().map(MissionData::from_genshin).collect(),
@@ -81,65 +67,66 @@ pub(crate) fn generate_quests(
                 }
             }
         }
+        // Star Rail missions have no LunarCore command, so they are not
+        // generated.
         GameExcelReader::StarRail(_) => {
-            match excel_reader.read_excel_data::<MissionElement>(resources, "MainMission") {
-                Ok(data) => data.into_iter().map(MissionData::from_star_rail).collect(),
-                Err(e) => {
-                    let error_msg = format!("Failed to read Main Mission: {}", e);
-                    output_log(app_handle, "error", &error_msg);
-                    return Err(error_msg);
-                }
-            }
+            let error_msg = "Quests are only generated for Genshin Impact".to_string();
+            output_log(app_handle, "error", &error_msg);
+            return Err(error_msg);
         }
-    };
+    })
+}
 
-    let mut total_main_quests = 0;
-    for main_quest in main_quests.iter() {
-        total_main_quests += 1;
+pub(crate) fn generate_quests(
+    app_handle: &tauri::AppHandle,
+    main_quests: &[MissionData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+) -> Result<(), String> {
+    let mut entries: ResultAccumulator<MainQuestResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_main_quests = 0;
+        for main_quest in main_quests.iter() {
+            total_main_quests += 1;
 
-        let name = text_map.get(&main_quest.name.to_string()).cloned();
-        let desc = main_quest
-            .description
-            .as_ref()
-            .and_then(|desc| text_map.get(&desc.to_string()).cloned());
+            let name = text_map.get(&main_quest.name.to_string()).cloned();
+            let desc = main_quest
+                .description
+                .as_ref()
+                .and_then(|desc| text_map.get(&desc.to_string()).cloned());
 
-        let main_quest_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Quests(m) if m.id == main_quest.id))
-            .and_then(|r| {
-                if let ResultData::Quests(m) = r {
-                    Some(m)
-                } else {
-                    None
-                }
-            });
+            let main_quest_result = entries.get_mut(main_quest.id);
 
-        if let Some(existing_main_quest) = main_quest_result {
-            existing_main_quest
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_main_quest
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
-            result.push(ResultData::Quests(MainQuestResult {
-                id: main_quest.id,
-                name: names,
-                description: descriptions,
-                category: Category::Quests,
-                commands: main_quest.commands.clone(),
-            }))
+            if let Some(existing_main_quest) = main_quest_result {
+                existing_main_quest
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_main_quest
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+                entries.insert(
+                    main_quest.id,
+                    MainQuestResult {
+                        id: main_quest.id,
+                        name: names,
+                        description: descriptions,
+                        category: Category::Quests,
+                        commands: main_quest.commands.clone(),
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Main Quests added: {}", total_main_quests),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Main Quests added: {}", total_main_quests),
-    );
-    drop(main_quests);
+    result.extend(entries.into_results(ResultData::Quests));
     Ok(())
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 
@@ -11,21 +11,21 @@ use crate::{
 
 use super::{
     commands::{generate_command, CommandMap},
-    output_log, GameExcelReader, ResultData,
+    output_log, GameExcelReader, ResultAccumulator, ResultData, TextHashes,
 };
 
 #[derive(Serialize)]
 pub struct WeaponResult {
     pub id: i64,
-    pub name: HashMap<Language, String>,
-    pub description: HashMap<Language, String>,
+    pub name: BTreeMap<Language, String>,
+    pub description: BTreeMap<Language, String>,
     pub icon: String,
     pub rarity: i64,
     pub category: Category,
     pub commands: CommandMap,
 }
 
-struct WeaponsData {
+pub(crate) struct WeaponsData {
     rarity: i64,
     id: i64,
     name: i64,
@@ -56,7 +56,7 @@ impl WeaponsData {
 
     fn from_star_rail(star_rail_weapon: LightCones) -> Self {
         let commands = generate_command(
-            Category::Weapons,
+            Category::LightCones,
             star_rail_weapon.equipment_id as u32,
             "/give",
             super::commands::GameType::HonkaiStarRail,
@@ -75,19 +75,18 @@ impl WeaponsData {
     }
 }
 
-pub(crate) fn generate_weapons<G>(
+impl TextHashes for WeaponsData {
+    fn text_hashes(&self) -> impl Iterator<Item = i64> {
+        [Some(self.name), self.description].into_iter().flatten()
+    }
+}
+
+pub(crate) fn read_weapons(
     app_handle: &tauri::AppHandle,
     resources: &str,
-    lang: &Language,
-    text_map: &TextMap,
-    result: &mut Vec<ResultData>,
     excel_reader: &GameExcelReader,
-    get_image: G,
-) -> Result<(), String>
-where
-    G: Fn(&str, &str, &str) -> String,
-{
-    let weapons: Vec<WeaponsData> = match excel_reader {
+) -> Result<Vec<WeaponsData>, String> {
+    Ok(match excel_reader {
         GameExcelReader::GenshinImpact(_) => {
             match excel_reader.read_excel_data::<Weapon>(resources, "WeaponExcelConfigData") {
                 Ok(data) => data.into_iter().map(WeaponsData::from_genshin).collect(),
@@ -108,58 +107,67 @@ where
                 }
             }
         }
-    };
-    let mut total_weapons = 0;
-    for weapon in weapons.iter() {
-        total_weapons += 1;
+    })
+}
+
+pub(crate) fn generate_weapons<G>(
+    app_handle: &tauri::AppHandle,
+    weapons: &[WeaponsData],
+    text_maps: &[(Language, TextMap)],
+    result: &mut Vec<ResultData>,
+    get_image: G,
+) -> Result<(), String>
+where
+    G: Fn(&str, &str, &str) -> String,
+{
+    let mut entries: ResultAccumulator<WeaponResult> = ResultAccumulator::default();
+    for (lang, text_map) in text_maps {
+        let mut total_weapons = 0;
+        for weapon in weapons.iter() {
+            total_weapons += 1;
 
-        let name = text_map.get(&weapon.name.to_string()).cloned();
-        let desc = weapon
-            .description
-            .map(|hash| text_map.get(&hash.to_string()).cloned())
-            .unwrap_or_default();
+            let name = text_map.get(&weapon.name.to_string()).cloned();
+            let desc = weapon
+                .description
+                .map(|hash| text_map.get(&hash.to_string()).cloned())
+                .unwrap_or_default();
 
-        let image = get_image("genshin-impact", &weapon.icon, "weapons");
+            let image = get_image("genshin-impact", &weapon.icon, "weapons");
 
-        let weapon_result = result
-            .iter_mut()
-            .find(|r| matches!(r, ResultData::Weapons(w) if w.id == weapon.id))
-            .and_then(|r| {
-                if let ResultData::Weapons(w) = r {
-                    Some(w)
-                } else {
-                    None
-                }
-            });
+            let weapon_result = entries.get_mut(weapon.id);
 
-        if let Some(existing_weapon) = weapon_result {
-            existing_weapon
-                .name
-                .insert(lang.clone(), name.unwrap_or_default());
-            existing_weapon
-                .description
-                .insert(lang.clone(), desc.unwrap_or_default());
-        } else {
-            let mut names = HashMap::new();
-            names.insert(lang.clone(), name.unwrap_or_default());
-            let mut descriptions = HashMap::new();
-            descriptions.insert(lang.clone(), desc.unwrap_or_default());
-            result.push(ResultData::Weapons(WeaponResult {
-                id: weapon.id,
-                name: names,
-                description: descriptions,
-                rarity: weapon.rarity,
-                category: weapon.category.clone(),
-                commands: weapon.commands.clone(),
-                icon: image,
-            }))
+            if let Some(existing_weapon) = weapon_result {
+                existing_weapon
+                    .name
+                    .insert(lang.clone(), name.unwrap_or_default());
+                existing_weapon
+                    .description
+                    .insert(lang.clone(), desc.unwrap_or_default());
+            } else {
+                let mut names = BTreeMap::new();
+                names.insert(lang.clone(), name.unwrap_or_default());
+                let mut descriptions = BTreeMap::new();
+                descriptions.insert(lang.clone(), desc.unwrap_or_default());
+                entries.insert(
+                    weapon.id,
+                    WeaponResult {
+                        id: weapon.id,
+                        name: names,
+                        description: descriptions,
+                        rarity: weapon.rarity,
+                        category: weapon.category.clone(),
+                        commands: weapon.commands.clone(),
+                        icon: image,
+                    },
+                )
+            }
         }
+        output_log(
+            app_handle,
+            "info",
+            &format!("Total Weapons added: {}", total_weapons),
+        );
     }
-    output_log(
-        app_handle,
-        "info",
-        &format!("Total Weapons added: {}", total_weapons),
-    );
-    drop(weapons);
+    result.extend(entries.into_results(ResultData::Weapons));
     Ok(())
 }
@@ -10,16 +10,26 @@ mod utility;
 use crate::download::download_resources;
 use crate::generate::generate_handbook;
 use crate::generate::list::get_list_text_map;
-use crate::search::gi::{find, get_category, get_path_handbook, update_path_handbook};
-use crate::structure::gm::Gmhandbook;
+use crate::search::diff::diff_handbooks;
+use crate::search::export::export_handbook;
+use crate::search::gi::{
+    find, get_category, get_path_handbook, list_handbooks, register_handbook,
+    unregister_handbook, update_path_handbook,
+};
+use crate::search::item::get_item;
+use crate::search::registry::HandbookRegistry;
+use crate::search::resolve::resolve_ids;
+use crate::search::suggest::suggest;
+use crate::search::watch::{watch_handbook, HandbookWatcher};
 use lazy_static::lazy_static;
 use log::error;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use tauri::Manager;
 
 lazy_static! {
-    static ref HANDBOOK_CONTENT: RwLock<Gmhandbook> = RwLock::new(Vec::new());
+    static ref HANDBOOKS: RwLock<HandbookRegistry> = RwLock::new(HandbookRegistry::default());
     static ref HANDBOOK_PATH: RwLock<String> = RwLock::new(String::new());
+    static ref HANDBOOK_WATCHER: Mutex<Option<HandbookWatcher>> = Mutex::new(None);
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -48,6 +58,15 @@ pub fn run() {
             update_path_handbook,
             get_path_handbook,
             get_category,
+            register_handbook,
+            unregister_handbook,
+            list_handbooks,
+            watch_handbook,
+            export_handbook,
+            diff_handbooks,
+            suggest,
+            get_item,
+            resolve_ids,
             get_list_text_map,
             download_resources,
         ])
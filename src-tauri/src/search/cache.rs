@@ -0,0 +1,215 @@
+use std::fs::{self, File, Metadata};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::UNIX_EPOCH;
+
+use bincode::config::Config;
+use bincode::error::DecodeError;
+use bincode::{Decode, Encode};
+use log::{info, warn};
+use memmap2::Mmap;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::search::index::HandbookIndex;
+use crate::search::txt::ParseReport;
+use crate::structure::gm::Gmhandbook;
+
+const MAGIC: [u8; 8] = *b"HBFCACHE";
+/// Bumped whenever the encoding of the cache changes in a way the crate
+/// version does not capture.
+const FORMAT: u32 = 6;
+/// Most bytes decoding a cache may allocate, so a corrupt length cannot
+/// request a huge allocation.
+const LIMIT: usize = 1 << 30;
+
+/// Everything kept in the cache of a handbook file.
+#[derive(Encode, Decode)]
+pub struct HandbookData {
+    pub content: Gmhandbook,
+    pub index: HandbookIndex,
+    /// Problems found while parsing a txt handbook, so they are still
+    /// reported when the handbook comes from the cache.
+    pub report: Option<ParseReport>,
+}
+
+/// The source file a cache was built from.
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceKey {
+    pub size: u64,
+    /// Modification time in nanoseconds since the Unix epoch, 0 when unknown.
+    pub modified: u64,
+    /// XXH3 hash of the file contents.
+    pub hash: u64,
+}
+
+#[derive(Encode, Decode)]
+struct Header {
+    magic: [u8; 8],
+    format: u32,
+    version: String,
+    source: SourceKey,
+}
+
+impl Header {
+    fn new(source: SourceKey) -> Self {
+        Header {
+            magic: MAGIC,
+            format: FORMAT,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            source,
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        self.magic == MAGIC && self.format == FORMAT && self.version == env!("CARGO_PKG_VERSION")
+    }
+}
+
+/// Path of the cache kept next to a handbook, e.g. `gmhandbook.json.cache`.
+pub fn cache_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".cache");
+    path.with_file_name(name)
+}
+
+/// Loads a handbook and its search index from the binary cache next to
+/// `path`, falling back to `parse` and rewriting the cache when it is missing,
+/// corrupt or stale. The cache is decoded in full, and its index checked
+/// against the decoded entries, before it is used.
+///
+/// The cache is trusted without hashing the source when the size and mtime
+/// still match. When only the mtime changed, e.g. after the file was copied,
+/// the source is hashed and the cache reused if the contents are unchanged.
+/// Failing to write the cache is logged and otherwise ignored.
+pub fn load(
+    path: &Path,
+    parse: impl FnOnce(&Path) -> Result<(Gmhandbook, Option<ParseReport>), String>,
+) -> Result<HandbookData, String> {
+    let metadata =
+        fs::metadata(path).map_err(|e| format!("Failed to read file metadata: {}", e))?;
+    let size = metadata.len();
+    let modified = modified(&metadata);
+    let cache = cache_path(path);
+
+    let mut hash = None;
+    if let Some((cached, mmap, offset)) = open_cache(&cache) {
+        let fresh = cached.size == size
+            && (cached.modified == modified || {
+                let source_hash = hash_file(path)?;
+                hash = Some(source_hash);
+                cached.hash == source_hash
+            });
+        if fresh {
+            let decoded = decode(&mmap[offset..]).and_then(|loaded| {
+                if loaded.index.is_consistent(loaded.content.len()) {
+                    Ok(loaded)
+                } else {
+                    Err(DecodeError::Other("index does not match the handbook"))
+                }
+            });
+            // Unmapped before the cache may be replaced below.
+            drop(mmap);
+            match decoded {
+                Ok(loaded) => {
+                    if cached.modified != modified {
+                        let source = SourceKey { modified, ..cached };
+                        write_logged(&cache, source, &loaded);
+                    }
+                    return Ok(loaded);
+                }
+                Err(e) => warn!("Ignoring corrupt handbook cache {}: {}", cache.display(), e),
+            }
+        }
+    }
+
+    let hash = match hash {
+        Some(hash) => hash,
+        None => hash_file(path)?,
+    };
+    let (content, report) = parse(path)?;
+    let index = HandbookIndex::build(&content);
+    let loaded = HandbookData {
+        content,
+        index,
+        report,
+    };
+    let source = SourceKey {
+        size,
+        modified,
+        hash,
+    };
+    write_logged(&cache, source, &loaded);
+    Ok(loaded)
+}
+
+fn modified(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn hash_file(path: &Path) -> Result<u64, String> {
+    let content = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(xxh3_64(&content))
+}
+
+/// Maps the cache and reads its header, returning the source it was built
+/// from and where the handbook starts.
+fn open_cache(cache: &Path) -> Option<(SourceKey, Mmap, usize)> {
+    let file = File::open(cache).ok()?;
+    // SAFETY: caches are only ever replaced by renaming a new file over them,
+    // never modified in place, so the mapped file does not change under us.
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    let (header, offset): (Header, usize) = bincode::decode_from_slice(&mmap, config()).ok()?;
+    header.is_current().then_some((header.source, mmap, offset))
+}
+
+fn config() -> impl Config {
+    bincode::config::standard().with_limit::<LIMIT>()
+}
+
+fn decode(bytes: &[u8]) -> Result<HandbookData, DecodeError> {
+    let (loaded, _) = bincode::decode_from_slice(bytes, config())?;
+    Ok(loaded)
+}
+
+fn write_logged(cache: &Path, source: SourceKey, data: &HandbookData) {
+    match write(cache, source, data) {
+        Ok(()) => info!("Wrote handbook cache {}", cache.display()),
+        Err(e) => warn!("Failed to write handbook cache {}: {}", cache.display(), e),
+    }
+}
+
+/// Writes the cache to a temporary file first, so a reader never sees it
+/// half-written. Every write gets its own temporary file, so concurrent
+/// writers, e.g. a watcher reload and a manual load, cannot interleave.
+fn write(cache: &Path, source: SourceKey, data: &HandbookData) -> Result<(), String> {
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+    let mut temporary = cache.as_os_str().to_os_string();
+    temporary.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temporary = PathBuf::from(temporary);
+
+    let written = File::create(&temporary)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            let config = config();
+            bincode::encode_into_std_write(Header::new(source), &mut writer, config)
+                .and_then(|_| bincode::encode_into_std_write(data, &mut writer, config))
+                .map_err(|e| e.to_string())?;
+            writer.flush().map_err(|e| e.to_string())
+        })
+        .and_then(|_| fs::rename(&temporary, cache).map_err(|e| e.to_string()));
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    written
+}
@@ -0,0 +1,330 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use log::info;
+use serde::Serialize;
+
+use crate::search::export::ExportFormat;
+use crate::search::gi::{load_handbook_file, GmError};
+use crate::structure::gm::{Category, Gmhandbook, GmhandbookElement, NameUnion};
+
+/// Language key of names that are the same in every language.
+pub const ANY_LANGUAGE: &str = "*";
+
+/// An entry only found in one of the handbooks.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DiffEntry {
+    pub id: i64,
+    pub category: Category,
+    pub names: BTreeMap<String, String>,
+}
+
+/// A name that differs between the handbooks in one language.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct NameChange {
+    pub language: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// An entry found in both handbooks under the same category and ID.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RenamedEntry {
+    pub id: i64,
+    pub category: Category,
+    pub changes: Vec<NameChange>,
+}
+
+/// An entry whose ID moved to another category.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RecategorizedEntry {
+    pub id: i64,
+    pub old_category: Category,
+    pub new_category: Category,
+    pub changes: Vec<NameChange>,
+}
+
+/// Changes between two versions of a handbook, each list sorted by ID.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct HandbookDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub renamed: Vec<RenamedEntry>,
+    pub recategorized: Vec<RecategorizedEntry>,
+    /// Entries found in both handbooks without any change.
+    pub unchanged: usize,
+}
+
+fn names(item: &GmhandbookElement) -> BTreeMap<String, String> {
+    match &item.name {
+        NameUnion::Description(names) => names
+            .iter()
+            .map(|(language, name)| (language.clone(), name.clone()))
+            .collect(),
+        NameUnion::String(name) => BTreeMap::from([(ANY_LANGUAGE.to_string(), name.clone())]),
+    }
+}
+
+/// Names that differ in a language both entries have a name in. Languages
+/// added or dropped by a handbook are not renames.
+fn name_changes(old: &GmhandbookElement, new: &GmhandbookElement) -> Vec<NameChange> {
+    let old_names = names(old);
+    let new_names = names(new);
+    old_names
+        .into_iter()
+        .filter_map(|(language, old)| {
+            let new = new_names
+                .get(&language)
+                .or_else(|| new_names.get(ANY_LANGUAGE))?;
+            (*new != old).then(|| NameChange {
+                language,
+                old,
+                new: new.clone(),
+            })
+        })
+        .collect()
+}
+
+fn diff_entry(item: &GmhandbookElement) -> DiffEntry {
+    DiffEntry {
+        id: item.id,
+        category: item.category.clone(),
+        names: names(item),
+    }
+}
+
+impl HandbookDiff {
+    /// Matches entries of both handbooks by category and ID. Entries left
+    /// over on both sides with the same ID are reported as recategorized,
+    /// the others as added or removed.
+    pub fn new(old: &Gmhandbook, new: &Gmhandbook) -> Self {
+        let key = |item: &GmhandbookElement| (item.category.as_str(), item.id);
+        let new_by_key: HashMap<_, &GmhandbookElement> =
+            new.iter().map(|item| (key(item), item)).collect();
+        let old_by_key: HashMap<_, &GmhandbookElement> =
+            old.iter().map(|item| (key(item), item)).collect();
+
+        let mut diff = HandbookDiff::default();
+        let mut removed: BTreeMap<i64, Vec<&GmhandbookElement>> = BTreeMap::new();
+        for item in old {
+            match new_by_key.get(&key(item)) {
+                Some(other) => {
+                    let changes = name_changes(item, other);
+                    if changes.is_empty() {
+                        diff.unchanged += 1;
+                    } else {
+                        diff.renamed.push(RenamedEntry {
+                            id: item.id,
+                            category: item.category.clone(),
+                            changes,
+                        });
+                    }
+                }
+                None => removed.entry(item.id).or_default().push(item),
+            }
+        }
+
+        for item in new
+            .iter()
+            .filter(|item| !old_by_key.contains_key(&key(item)))
+        {
+            let moved = removed
+                .get_mut(&item.id)
+                .and_then(|items| (!items.is_empty()).then(|| items.remove(0)));
+            match moved {
+                Some(previous) => diff.recategorized.push(RecategorizedEntry {
+                    id: item.id,
+                    old_category: previous.category.clone(),
+                    new_category: item.category.clone(),
+                    changes: name_changes(previous, item),
+                }),
+                None => diff.added.push(diff_entry(item)),
+            }
+        }
+        diff.removed = removed
+            .values()
+            .flatten()
+            .map(|item| diff_entry(item))
+            .collect();
+
+        diff.added
+            .sort_by_key(|entry| (entry.id, entry.category.as_str()));
+        diff.renamed
+            .sort_by_key(|entry| (entry.id, entry.category.as_str()));
+        diff.recategorized
+            .sort_by_key(|entry| (entry.id, entry.new_category.as_str()));
+        diff
+    }
+
+    pub fn write_report(&self, format: ExportFormat, writer: impl Write) -> Result<(), String> {
+        let written: Result<(), Box<dyn std::error::Error>> = match format {
+            ExportFormat::Json => serde_json::to_writer_pretty(writer, self).map_err(Into::into),
+            ExportFormat::Txt => self.write_txt(writer),
+            ExportFormat::Csv => self.write_csv(writer),
+        };
+        written.map_err(|e| format!("Failed to write diff report: {}", e))
+    }
+
+    fn write_txt(&self, mut writer: impl Write) -> Result<(), Box<dyn std::error::Error>> {
+        let names = |names: &BTreeMap<String, String>| {
+            names
+                .iter()
+                .map(|(language, name)| format!("{}: {}", language, name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let changes = |changes: &[NameChange]| {
+            changes
+                .iter()
+                .map(|change| format!("{}: {} -> {}", change.language, change.old, change.new))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        writeln!(writer, "# Added ({})", self.added.len())?;
+        for entry in &self.added {
+            writeln!(
+                writer,
+                "{} [{}] {}",
+                entry.id,
+                entry.category,
+                names(&entry.names)
+            )?;
+        }
+        writeln!(writer, "\n# Removed ({})", self.removed.len())?;
+        for entry in &self.removed {
+            writeln!(
+                writer,
+                "{} [{}] {}",
+                entry.id,
+                entry.category,
+                names(&entry.names)
+            )?;
+        }
+        writeln!(writer, "\n# Renamed ({})", self.renamed.len())?;
+        for entry in &self.renamed {
+            writeln!(
+                writer,
+                "{} [{}] {}",
+                entry.id,
+                entry.category,
+                changes(&entry.changes)
+            )?;
+        }
+        writeln!(writer, "\n# Recategorized ({})", self.recategorized.len())?;
+        for entry in &self.recategorized {
+            write!(
+                writer,
+                "{} [{} -> {}]",
+                entry.id, entry.old_category, entry.new_category
+            )?;
+            if !entry.changes.is_empty() {
+                write!(writer, " {}", changes(&entry.changes))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// One row per name, so every change can be filtered by language.
+    fn write_csv(&self, writer: impl Write) -> Result<(), Box<dyn std::error::Error>> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record([
+            "change",
+            "id",
+            "category",
+            "old_category",
+            "language",
+            "old_name",
+            "new_name",
+        ])?;
+        for (change, entries) in [("added", &self.added), ("removed", &self.removed)] {
+            for entry in entries {
+                for (language, name) in &entry.names {
+                    let (old, new) = match change {
+                        "added" => ("", name.as_str()),
+                        _ => (name.as_str(), ""),
+                    };
+                    let id = entry.id.to_string();
+                    let category = entry.category.as_str();
+                    csv.write_record([change, &id, category, "", language, old, new])?;
+                }
+            }
+        }
+        for entry in &self.renamed {
+            for name in &entry.changes {
+                csv.write_record([
+                    "renamed",
+                    &entry.id.to_string(),
+                    entry.category.as_str(),
+                    "",
+                    &name.language,
+                    &name.old,
+                    &name.new,
+                ])?;
+            }
+        }
+        for entry in &self.recategorized {
+            let id = entry.id.to_string();
+            let (category, old_category) =
+                (entry.new_category.as_str(), entry.old_category.as_str());
+            if entry.changes.is_empty() {
+                csv.write_record(["recategorized", &id, category, old_category, "", "", ""])?;
+            }
+            for name in &entry.changes {
+                csv.write_record([
+                    "recategorized",
+                    &id,
+                    category,
+                    old_category,
+                    &name.language,
+                    &name.old,
+                    &name.new,
+                ])?;
+            }
+        }
+        csv.flush()?;
+        Ok(())
+    }
+}
+
+/// Compares two handbook files, e.g. before and after a game patch. With
+/// `output`, the report is also written there as JSON, TXT or CSV, depending
+/// on its extension.
+#[tauri::command(async)]
+pub fn diff_handbooks(
+    old_path: &str,
+    new_path: &str,
+    output: Option<String>,
+) -> Result<HandbookDiff, String> {
+    if old_path.is_empty() || new_path.is_empty() {
+        return Err(GmError::PathEmpty.to_string());
+    }
+    let (old, _) = load_handbook_file(Path::new(old_path))?;
+    let (new, _) = load_handbook_file(Path::new(new_path))?;
+    let diff = HandbookDiff::new(&old, &new);
+    info!(
+        "Compared {} with {}: {} added, {} removed, {} renamed, {} recategorized",
+        old_path,
+        new_path,
+        diff.added.len(),
+        diff.removed.len(),
+        diff.renamed.len(),
+        diff.recategorized.len()
+    );
+
+    if let Some(output) = output.filter(|output| !output.is_empty()) {
+        let path = Path::new(&output);
+        let format = ExportFormat::from_path(path)
+            .ok_or_else(|| format!("Unsupported report format: {}", output))?;
+        let file = File::create(path).map_err(|e| format!("Failed to create file: {}", e))?;
+        let mut writer = BufWriter::new(file);
+        diff.write_report(format, &mut writer)?;
+        writer
+            .flush()
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    Ok(diff)
+}
@@ -0,0 +1,368 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::search::filter::SearchFilter;
+use crate::search::gi::{
+    search_handbooks, select_handbooks, GmError, SearchRequest, SearchResult, SearchSource,
+};
+use crate::search::registry::Game;
+use crate::structure::gm::{Category, NameUnion};
+use crate::structure::handbook::Language;
+use crate::HANDBOOKS;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A spreadsheet with a header row and one column per language.
+    Csv,
+    /// `id : name` lines under category headers, as GC and LunarCore write
+    /// their handbooks.
+    Txt,
+    /// An array of objects, with names and descriptions keyed by language.
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "txt" => Some(ExportFormat::Txt),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportColumn {
+    Id,
+    /// One column per exported language.
+    Name,
+    /// One column per exported language.
+    Description,
+    Category,
+    Rarity,
+    Handbook,
+    Game,
+    Score,
+}
+
+impl ExportColumn {
+    pub const DEFAULT: [ExportColumn; 3] =
+        [ExportColumn::Id, ExportColumn::Name, ExportColumn::Category];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExportColumn::Id => "id",
+            ExportColumn::Name => "name",
+            ExportColumn::Description => "description",
+            ExportColumn::Category => "category",
+            ExportColumn::Rarity => "rarity",
+            ExportColumn::Handbook => "handbook",
+            ExportColumn::Game => "game",
+            ExportColumn::Score => "score",
+        }
+    }
+
+    fn per_language(&self) -> bool {
+        matches!(self, ExportColumn::Name | ExportColumn::Description)
+    }
+}
+
+/// Where and how `export_handbook` writes the matches.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ExportOptions {
+    pub path: String,
+    /// Guessed from the extension of `path` when not given.
+    pub format: Option<ExportFormat>,
+    /// Defaults to [`ExportColumn::DEFAULT`].
+    pub columns: Option<Vec<ExportColumn>>,
+    /// Languages of the name and description columns. Defaults to the
+    /// languages that were searched.
+    pub languages: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ExportSummary {
+    pub path: String,
+    pub format: ExportFormat,
+    pub entries: usize,
+}
+
+/// Writes every entry matching a `find` query to a file, in the handbook
+/// order for an empty query and in ranking order otherwise. Without search
+/// term and filters, whole handbooks are exported.
+#[tauri::command(async)]
+pub fn export_handbook(
+    search: &str,
+    language: &str,
+    filter: Option<SearchFilter>,
+    descriptions: Option<bool>,
+    handbook: Option<String>,
+    options: ExportOptions,
+) -> Result<ExportSummary, String> {
+    if options.path.is_empty() {
+        return Err(GmError::PathEmpty.to_string());
+    }
+    if options.languages.as_ref().is_some_and(Vec::is_empty) {
+        return Err("No language to export".to_string());
+    }
+    let path = Path::new(&options.path);
+    let format = options
+        .format
+        .or_else(|| ExportFormat::from_path(path))
+        .ok_or_else(|| format!("Unsupported export format: {}", options.path))?;
+    let columns = options
+        .columns
+        .unwrap_or_else(|| ExportColumn::DEFAULT.to_vec());
+
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    let sources: Vec<SearchSource> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .map(SearchSource::from)
+        .collect();
+    let request = SearchRequest {
+        search,
+        language,
+        filter: filter.unwrap_or_default(),
+        descriptions: descriptions.unwrap_or_default(),
+        offset: 0,
+        limit: None,
+    };
+    let response = search_handbooks(&sources, &request).map_err(|e| e.to_string())?;
+
+    let languages = match options.languages {
+        Some(languages) => languages
+            .iter()
+            .map(|language| Language::from_str(language).map(|language| language.key()))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => response.languages,
+    };
+
+    let file = File::create(path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    write_export(&response.items, format, &columns, &languages, &mut writer)?;
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    info!(
+        "Exported {} entries to {}",
+        response.items.len(),
+        options.path
+    );
+    Ok(ExportSummary {
+        path: options.path,
+        format,
+        entries: response.items.len(),
+    })
+}
+
+/// Writes `results` in `format`, with names and descriptions in `languages`.
+pub fn write_export(
+    results: &[SearchResult],
+    format: ExportFormat,
+    columns: &[ExportColumn],
+    languages: &[&str],
+    writer: impl Write,
+) -> Result<(), String> {
+    match format {
+        ExportFormat::Csv => write_csv(results, columns, languages, writer),
+        ExportFormat::Txt => write_txt(results, columns, languages, writer),
+        ExportFormat::Json => write_json(results, columns, languages, writer),
+    }
+    .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+fn name<'a>(result: &'a SearchResult, language: &str) -> Option<&'a str> {
+    match &result.item.name {
+        NameUnion::Description(names) => names.get(language).map(String::as_str),
+        NameUnion::String(name) => Some(name),
+    }
+}
+
+fn description<'a>(result: &'a SearchResult, language: &str) -> Option<&'a str> {
+    let descriptions = result.item.description.as_ref()?;
+    descriptions.get(language).map(String::as_str)
+}
+
+/// Value of a column that is the same in every language.
+fn cell(result: &SearchResult, column: ExportColumn) -> String {
+    match column {
+        ExportColumn::Id => result.item.id.to_string(),
+        ExportColumn::Category => result.item.category.to_string(),
+        ExportColumn::Rarity => result
+            .item
+            .rarity
+            .map(|rarity| rarity.to_string())
+            .unwrap_or_default(),
+        ExportColumn::Handbook => result.handbook.clone().unwrap_or_default(),
+        ExportColumn::Game => result.game.map(|game| game.to_string()).unwrap_or_default(),
+        ExportColumn::Score => result.score.to_string(),
+        ExportColumn::Name | ExportColumn::Description => String::new(),
+    }
+}
+
+/// Values of a column, one per language for names and descriptions.
+fn cells(result: &SearchResult, column: ExportColumn, languages: &[&str]) -> Vec<String> {
+    let text = match column {
+        ExportColumn::Name => name,
+        ExportColumn::Description => description,
+        _ => return vec![cell(result, column)],
+    };
+    languages
+        .iter()
+        .map(|language| text(result, language).unwrap_or_default().to_string())
+        .collect()
+}
+
+fn write_csv(
+    results: &[SearchResult],
+    columns: &[ExportColumn],
+    languages: &[&str],
+    writer: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut csv = csv::Writer::from_writer(writer);
+    let header: Vec<String> = columns
+        .iter()
+        .flat_map(|column| {
+            if column.per_language() {
+                languages
+                    .iter()
+                    .map(|language| format!("{}_{}", column.as_str(), language))
+                    .collect()
+            } else {
+                vec![column.as_str().to_string()]
+            }
+        })
+        .collect();
+    csv.write_record(&header)?;
+    for result in results {
+        let record: Vec<String> = columns
+            .iter()
+            .flat_map(|column| cells(result, *column, languages))
+            .collect();
+        csv.write_record(&record)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Header of a category's section, as `parse_txt` reads it back. Star Rail
+/// avatars are listed as characters but keep LunarCore's section name.
+fn section_name(category: &Category) -> &'static str {
+    match category {
+        Category::Avatars => "Avatars",
+        category => category.as_str(),
+    }
+}
+
+/// Writes `id : name` lines, followed by the other columns separated by
+/// ` : `, under a header for each category. Categories keep the order they
+/// first appear in, and Star Rail entries get LunarCore's `#` headers so
+/// the file can be loaded back as a handbook.
+fn write_txt(
+    results: &[SearchResult],
+    columns: &[ExportColumn],
+    languages: &[&str],
+    mut writer: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sections: Vec<(&Category, Vec<&SearchResult>)> = Vec::new();
+    for result in results {
+        match sections
+            .iter_mut()
+            .find(|(category, _)| **category == result.item.category)
+        {
+            Some((_, entries)) => entries.push(result),
+            None => sections.push((&result.item.category, vec![result])),
+        }
+    }
+
+    let extra: Vec<ExportColumn> = columns
+        .iter()
+        .copied()
+        .filter(|column| !matches!(column, ExportColumn::Id | ExportColumn::Category))
+        .collect();
+    for (position, (category, entries)) in sections.iter().enumerate() {
+        if position > 0 {
+            writeln!(writer)?;
+        }
+        let prefix = match entries[0].game {
+            Some(Game::StarRail) => "#",
+            _ => "//",
+        };
+        writeln!(writer, "{} {}", prefix, section_name(category))?;
+        for result in entries {
+            // The first language with a name, whose cell is then skipped.
+            let named = languages
+                .iter()
+                .position(|language| name(result, language).is_some());
+            let first = named
+                .and_then(|position| name(result, languages[position]))
+                .unwrap_or_default();
+            write!(writer, "{} : {}", result.item.id, first)?;
+            for column in &extra {
+                let mut values = cells(result, *column, languages);
+                let written = named.unwrap_or(0);
+                if *column == ExportColumn::Name && written < values.len() {
+                    values.remove(written);
+                }
+                for value in values {
+                    write!(writer, " : {}", value.replace(['\r', '\n'], " "))?;
+                }
+            }
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_json(
+    results: &[SearchResult],
+    columns: &[ExportColumn],
+    languages: &[&str],
+    writer: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entries: Vec<Map<String, Value>> = results
+        .iter()
+        .map(|result| {
+            columns
+                .iter()
+                .map(|column| {
+                    let value = match column {
+                        ExportColumn::Id => json!(result.item.id),
+                        ExportColumn::Rarity => json!(result.item.rarity),
+                        ExportColumn::Score => json!(result.score),
+                        ExportColumn::Name | ExportColumn::Description => {
+                            let text = match column {
+                                ExportColumn::Name => name,
+                                _ => description,
+                            };
+                            let texts: Map<String, Value> = languages
+                                .iter()
+                                .filter_map(|language| {
+                                    Some((language.to_string(), json!(text(result, language)?)))
+                                })
+                                .collect();
+                            Value::Object(texts)
+                        }
+                        _ => json!(cell(result, *column)),
+                    };
+                    (column.as_str().to_string(), value)
+                })
+                .collect()
+        })
+        .collect();
+    serde_json::to_writer_pretty(writer, &entries)?;
+    Ok(())
+}
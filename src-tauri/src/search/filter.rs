@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::structure::gm::{Category, GmhandbookElement, Type};
+
+/// Inclusive numeric bounds, used for rarities and IDs. A missing bound is open.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NumberRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+}
+
+impl NumberRange {
+    pub fn contains(&self, value: i64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// Structured filters applied by `find` before the limit.
+///
+/// Every filter that is set has to match; an empty list matches nothing.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SearchFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<Category>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rarity: Option<NumberRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<Type>>,
+}
+
+impl SearchFilter {
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_none() && self.rarity.is_none() && self.types.is_none()
+    }
+
+    pub fn matches(&self, item: &GmhandbookElement) -> bool {
+        if let Some(categories) = &self.categories {
+            // Compare display names the same way `get_category` lists them.
+            if !categories
+                .iter()
+                .any(|category| category.same_listing(&item.category))
+            {
+                return false;
+            }
+        }
+        if let Some(rarity) = &self.rarity {
+            if !item.rarity.is_some_and(|value| rarity.contains(value)) {
+                return false;
+            }
+        }
+        if let Some(types) = &self.types {
+            if !item
+                .gmhandbook_type
+                .as_ref()
+                .is_some_and(|value| types.contains(value))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
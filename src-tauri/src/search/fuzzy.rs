@@ -0,0 +1,155 @@
+/// Score given to a name that is exactly the query.
+pub const SCORE_EXACT: u32 = 1000;
+/// Score given to a name whose words are exactly the query words (punctuation ignored).
+pub const SCORE_EXACT_TOKENS: u32 = 950;
+/// Score given to a name that starts with the query.
+pub const SCORE_PREFIX: u32 = 900;
+/// Highest score of a description match, below the lowest score [`score`]
+/// can give a name, so name matches always rank first.
+pub const SCORE_DESCRIPTION_MAX: u32 = 300;
+
+/// Splits an already lowercased string into alphanumeric words.
+///
+/// Punctuation and whitespace are treated as separators, so `"amos' bow"` and
+/// `"amos bow"` produce the same tokens.
+pub fn tokenize(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Maximum number of edits tolerated for a query token of the given length.
+pub(crate) fn max_typos(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance (Levenshtein with adjacent transpositions).
+///
+/// Returns `None` as soon as the distance is known to exceed `limit`.
+pub fn edit_distance(a: &[char], b: &[char], limit: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > limit {
+        return None;
+    }
+    let width = b.len() + 1;
+    let mut prev_prev = vec![0; width];
+    let mut prev: Vec<usize> = (0..width).collect();
+    let mut current = vec![0; width];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut row_min = current[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(prev_prev[j - 2] + 1);
+            }
+            current[j] = value;
+            row_min = row_min.min(value);
+        }
+        if row_min > limit {
+            return None;
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= limit).then_some(distance)
+}
+
+/// Scores a single query token against a single name token, from 0 to 100.
+pub fn token_score(query: &str, token: &str) -> Option<u32> {
+    if query == token {
+        return Some(100);
+    }
+    if token.starts_with(query) {
+        return Some(80);
+    }
+    if token.contains(query) {
+        return Some(60);
+    }
+
+    let query_chars: Vec<char> = query.chars().collect();
+    let limit = max_typos(query_chars.len());
+    if limit == 0 {
+        return None;
+    }
+    let token_chars: Vec<char> = token.chars().collect();
+    let whole = edit_distance(&query_chars, &token_chars, limit);
+    // Also compare against the start of the token so typos made while still
+    // typing a long word are forgiven.
+    let partial = if token_chars.len() > query_chars.len() {
+        edit_distance(&query_chars, &token_chars[..query_chars.len()], limit)
+    } else {
+        None
+    };
+    let distance = match (whole, partial) {
+        (Some(a), Some(b)) => a.min(b),
+        (Some(d), None) | (None, Some(d)) => d,
+        (None, None) => return None,
+    };
+    Some(50 - 10 * distance as u32)
+}
+
+/// Scores how well `name` matches `query`. Both must already be lowercased.
+///
+/// Returns `None` when the name does not match at all. Higher is better:
+///
+/// * [`SCORE_EXACT`] for an exact match,
+/// * [`SCORE_EXACT_TOKENS`] when the words are identical but punctuation differs,
+/// * [`SCORE_PREFIX`] when the name starts with the query,
+/// * 350 to 700 when every query word matches a word of the name, either
+///   exactly, as a prefix, as a substring or within a small edit distance.
+///
+/// Longer names lose a few points so that, within a tier, the closest match
+/// comes first.
+pub fn score(query: &str, name: &str) -> Option<u32> {
+    let query = query.trim();
+    if query.is_empty() {
+        return None;
+    }
+    if name == query {
+        return Some(SCORE_EXACT);
+    }
+
+    let query_tokens = tokenize(query);
+    let name_tokens = tokenize(name);
+    let base = if !query_tokens.is_empty() && query_tokens == name_tokens {
+        SCORE_EXACT_TOKENS
+    } else if name.starts_with(query) || is_token_prefix(&query_tokens, &name_tokens) {
+        SCORE_PREFIX
+    } else {
+        if query_tokens.is_empty() {
+            return None;
+        }
+        let mut total = 0;
+        for query_token in &query_tokens {
+            total += name_tokens
+                .iter()
+                .filter_map(|token| token_score(query_token, token))
+                .max()?;
+        }
+        200 + total / query_tokens.len() as u32 * 5
+    };
+
+    let extra = name.chars().count().saturating_sub(query.chars().count());
+    Some(base - extra.min(49) as u32)
+}
+
+/// Whether the query words are a prefix of the name words, with the last query
+/// word allowed to be incomplete ("amos b" against "amos' bow").
+fn is_token_prefix(query: &[&str], name: &[&str]) -> bool {
+    match query.split_last() {
+        Some((last, rest)) if query.len() <= name.len() => {
+            rest.iter().zip(name).all(|(q, n)| q == n) && name[rest.len()].starts_with(last)
+        }
+        _ => false,
+    }
+}
@@ -1,9 +1,24 @@
-use crate::structure::gm::{Category, Gmhandbook, GmhandbookElement, NameUnion};
-use crate::{HANDBOOK_CONTENT, HANDBOOK_PATH};
+use crate::search::cache;
+use crate::search::filter::{NumberRange, SearchFilter};
+use crate::search::fuzzy;
+use crate::search::index::HandbookIndex;
+use crate::search::item::ItemSummary;
+use crate::search::language::LanguageMode;
+use crate::search::query::{parse_ids, Predicate, Query, Term};
+use crate::search::registry::{
+    Game, HandbookInfo, HandbookRegistry, HandbookTarget, LoadedHandbook, DEFAULT_HANDBOOK,
+};
+use crate::search::snippet::Snippet;
+use crate::search::txt::{parse_txt_file, ParseReport};
+use crate::search::watch;
+use crate::structure::gm::{Category, Gmhandbook, GmhandbookElement};
+use crate::{HANDBOOKS, HANDBOOK_PATH};
 use log::{info, warn};
-use std::collections::HashSet;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::path::Path;
 use std::str::FromStr;
 use tauri::ipc::InvokeError;
@@ -25,6 +40,18 @@ pub enum GmError {
     PathNotUpdated,
     #[error("Failed to write handbook content: {0}")]
     WriteHandbookContentError(String),
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("Unsupported language: {0}")]
+    UnsupportedLanguage(String),
+    #[error("No handbook is loaded for: {0}")]
+    HandbookNotFound(String),
+    #[error("Handbook name is reserved: {0}")]
+    ReservedHandbookName(String),
+    #[error("Handbook has errors: {0}")]
+    InvalidHandbook(String),
+    #[error("No {category} entry with ID {id}")]
+    ItemNotFound { category: Category, id: i64 },
 }
 
 impl From<serde_json::Error> for GmError {
@@ -39,49 +66,416 @@ impl From<GmError> for InvokeError {
     }
 }
 
-#[tauri::command]
-pub fn find(search: &str, language: &str, limit: Option<i64>) -> Result<Gmhandbook, String> {
-    if search.is_empty() {
-        return Err(GmError::EmptySearchTerm.to_string());
+/// What a search result was found by.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchField {
+    Name,
+    Description,
+    Id,
+}
+
+/// A matching entry, either the whole [`GmhandbookElement`] or an
+/// [`ItemSummary`] of it.
+#[derive(Serialize, Clone)]
+pub struct SearchResult<T = GmhandbookElement> {
+    #[serde(flatten)]
+    pub item: T,
+    pub score: u32,
+    /// Language of the name or description that matched, `None` for
+    /// language-independent names, ID and filter-only matches.
+    pub matched_language: Option<&'static str>,
+    /// `None` for filter-only queries.
+    pub matched_field: Option<MatchField>,
+    /// Matching part of the description, for description matches.
+    pub snippet: Option<Snippet>,
+    /// Registered handbook the entry comes from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handbook: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game: Option<Game>,
+}
+
+/// One page of search results, with what is needed to fetch the next one.
+#[derive(Serialize, Clone)]
+pub struct SearchResponse<T = GmhandbookElement> {
+    pub items: Vec<SearchResult<T>>,
+    /// Number of entries matching the query and filters, across all pages.
+    pub total: usize,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    /// Offset of the next page, or `None` when this is the last one.
+    pub next_offset: Option<usize>,
+    /// Search term and filters the results were computed with.
+    pub query: String,
+    pub filter: SearchFilter,
+    /// Languages whose names were searched, including detected ones.
+    pub languages: Vec<&'static str>,
+    /// Registered handbooks that were searched.
+    pub handbooks: Vec<String>,
+}
+
+/// A handbook to search, with its index.
+pub struct SearchSource<'a> {
+    pub handbook: &'a Gmhandbook,
+    pub index: &'a HandbookIndex,
+    /// Registry name and game, reported with every result.
+    pub name: Option<&'a str>,
+    pub game: Option<Game>,
+}
+
+impl<'a> From<&'a LoadedHandbook> for SearchSource<'a> {
+    fn from(loaded: &'a LoadedHandbook) -> Self {
+        SearchSource {
+            handbook: &loaded.content,
+            index: &loaded.index,
+            name: Some(&loaded.name),
+            game: Some(loaded.game),
+        }
     }
+}
 
-    let handbook_content = HANDBOOK_CONTENT
-        .read()
-        .map_err(|_| "Failed to read handbook content".to_string())?;
-    let result: Gmhandbook = handbook_content
-        .iter()
-        .filter(|item| {
-            let name = match &item.name {
-                NameUnion::Description(desc) => match language.to_lowercase().as_str() {
-                    "en" => desc.get("EN"),
-                    "jp" => desc.get("JP"),
-                    "id" => desc.get("ID"),
-                    "cht" => desc.get("CHT"),
-                    "th" => desc.get("TH"),
-                    "fr" => desc.get("FR"),
-                    "ru" => desc.get("RU"),
-                    "chs" => desc.get("CHS"),
-                    _ => desc.get("EN"),
-                },
-                NameUnion::String(ref s) => Some(s),
-                // Motherfucker error
-                // NameUnion::Object(ref s) => Some(&s.to_string()),
+/// An entry matching a query group, and how it matched.
+#[derive(Clone, Copy)]
+struct Hit {
+    entry: usize,
+    score: u32,
+    language: Option<&'static str>,
+    field: Option<MatchField>,
+}
+
+impl Hit {
+    fn from_index(
+        (entry, score, language): (usize, u32, Option<&'static str>),
+        field: MatchField,
+    ) -> Self {
+        Hit {
+            entry,
+            score,
+            language,
+            field: Some(field),
+        }
+    }
+}
+
+/// Everything `find` needs to run a search against a loaded handbook.
+#[derive(Default)]
+pub struct SearchRequest<'a> {
+    pub search: &'a str,
+    /// A language code, `all` to search every language or `auto` to pick the
+    /// languages from the script of the query.
+    pub language: &'a str,
+    pub filter: SearchFilter,
+    /// Also match descriptions. Description matches rank below every name
+    /// match and come with a snippet.
+    pub descriptions: bool,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+/// Runs a search against one or more handbooks and the indexes built from them.
+///
+/// The search term is parsed as a [`Query`]. Filters are applied before the
+/// offset and limit, so a page always holds the best matching entries that pass
+/// every filter. A query without free text lists every entry passing the
+/// filters, in file order. Results of all handbooks are ranked together; ties
+/// are broken by the order of `sources`.
+pub fn search_handbooks(
+    sources: &[SearchSource],
+    request: &SearchRequest,
+) -> Result<SearchResponse, GmError> {
+    search_handbooks_with(sources, request, |item, _, _| item.clone())
+}
+
+/// Same as [`search_handbooks`], with [`ItemSummary`] results that only carry
+/// what a result list shows.
+pub fn summarize_handbooks(
+    sources: &[SearchSource],
+    request: &SearchRequest,
+) -> Result<SearchResponse<ItemSummary>, GmError> {
+    search_handbooks_with(sources, request, ItemSummary::new)
+}
+
+/// Runs the search, building each result of the requested page with `view`
+/// from the entry, the language it matched in and the searched languages.
+fn search_handbooks_with<T>(
+    sources: &[SearchSource],
+    request: &SearchRequest,
+    view: impl Fn(&GmhandbookElement, Option<&str>, &[&str]) -> T,
+) -> Result<SearchResponse<T>, GmError> {
+    let query = Query::parse(request.search).map_err(GmError::InvalidQuery)?;
+    let mode = LanguageMode::from_str(request.language)
+        .map_err(|_| GmError::UnsupportedLanguage(request.language.to_string()))?;
+
+    let mut searched: Vec<&'static str> = Vec::new();
+    // Best hit per (source, entry).
+    let mut scores: HashMap<(usize, usize), Hit> = HashMap::new();
+    for (position, source) in sources.iter().enumerate() {
+        for group in &query.groups {
+            let (languages, hits) = search_group(
+                source.handbook,
+                source.index,
+                group,
+                &mode,
+                request.descriptions,
+            );
+            for language in languages {
+                if !searched.contains(&language) {
+                    searched.push(language);
+                }
+            }
+            for hit in hits {
+                if request.filter.matches(&source.handbook[hit.entry]) {
+                    let best = scores.entry((position, hit.entry)).or_insert(hit);
+                    if hit.score > best.score {
+                        *best = hit;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut matches: Vec<(usize, Hit)> = scores
+        .into_iter()
+        .map(|((position, _), hit)| (position, hit))
+        .collect();
+    // Ties are broken by handbook, then file order.
+    matches.sort_by_key(|(position, hit)| (Reverse(hit.score), *position, hit.entry));
+
+    let (words, phrases) = highlight_terms(&query);
+    let total = matches.len();
+    let items: Vec<SearchResult<T>> = matches
+        .into_iter()
+        .skip(request.offset)
+        .take(request.limit.unwrap_or(usize::MAX))
+        .map(|(position, hit)| {
+            let source = &sources[position];
+            let item = &source.handbook[hit.entry];
+            let snippet = match (hit.field, hit.language) {
+                (Some(MatchField::Description), Some(language)) => item
+                    .description
+                    .as_ref()
+                    .and_then(|descriptions| descriptions.get(language))
+                    .map(|description| Snippet::new(description, &words, &phrases)),
+                _ => None,
             };
-            if name.map_or(false, |n| n.to_lowercase().contains(&search.to_lowercase()))
-                || (item.id.to_string().to_lowercase() == search && language.to_uppercase() == "EN")
-            {
-                return true;
+            SearchResult {
+                item: view(item, hit.language, &searched),
+                score: hit.score,
+                matched_language: hit.language,
+                matched_field: hit.field,
+                snippet,
+                handbook: source.name.map(str::to_string),
+                game: source.game,
             }
-            false
         })
-        .take(limit.unwrap_or(i64::MAX) as usize)
-        .cloned()
         .collect();
-    Ok(result)
+    let end = request.offset.saturating_add(items.len());
+
+    Ok(SearchResponse {
+        items,
+        total,
+        offset: request.offset,
+        limit: request.limit,
+        next_offset: (end < total).then_some(end),
+        query: request.search.to_string(),
+        filter: request.filter.clone(),
+        languages: searched,
+        handbooks: sources
+            .iter()
+            .filter_map(|source| source.name.map(str::to_string))
+            .collect(),
+    })
+}
+
+/// ID ranges searched by free text made of a single ID pattern, in any language.
+fn id_ranges(text: &str) -> Option<Vec<NumberRange>> {
+    if !text.starts_with(|c: char| c.is_ascii_digit()) || text.contains(char::is_whitespace) {
+        return None;
+    }
+    parse_ids(text).ok()
+}
+
+/// Words and phrases of the query to highlight in snippets.
+fn highlight_terms(query: &Query) -> (Vec<&str>, Vec<&str>) {
+    let mut words = Vec::new();
+    let mut phrases = Vec::new();
+    for term in query.groups.iter().flatten().filter(|term| !term.negated) {
+        match &term.predicate {
+            Predicate::Text(text) => words.extend(fuzzy::tokenize(text)),
+            Predicate::Phrase(phrase) => phrases.push(phrase.as_str()),
+            _ => {}
+        }
+    }
+    (words, phrases)
+}
+
+/// Scores the entries matching every term of one query group, and returns
+/// them with the languages that were searched.
+fn search_group(
+    handbook: &Gmhandbook,
+    index: &HandbookIndex,
+    group: &[Term],
+    mode: &LanguageMode,
+    descriptions: bool,
+) -> (Vec<&'static str>, Vec<Hit>) {
+    let positive = |term: &&Term| !term.negated;
+    let text: Vec<&str> = group
+        .iter()
+        .filter(positive)
+        .filter_map(|term| match &term.predicate {
+            Predicate::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let phrase = group
+        .iter()
+        .filter(positive)
+        .find_map(|term| match &term.predicate {
+            Predicate::Phrase(phrase) => Some(phrase.as_str()),
+            _ => None,
+        });
+
+    let text = text.join(" ");
+    let languages = mode.keys(&format!("{} {}", text, phrase.unwrap_or_default()));
+    let seed = match (text.is_empty(), phrase) {
+        (false, _) => Some(text.as_str()),
+        (true, phrase) => phrase,
+    };
+    let mut candidates: Vec<Hit> = match seed {
+        Some(seed) => index
+            .search(seed, &languages)
+            .into_iter()
+            .map(|hit| Hit::from_index(hit, MatchField::Name))
+            .collect(),
+        None => (0..handbook.len())
+            .map(|entry| Hit {
+                entry,
+                score: 0,
+                language: None,
+                field: None,
+            })
+            .collect(),
+    };
+    if let (Some(seed), true) = (seed, descriptions) {
+        let found: HashSet<usize> = candidates.iter().map(|hit| hit.entry).collect();
+        candidates.extend(
+            index
+                .search_descriptions(seed, &languages)
+                .into_iter()
+                .filter(|(entry, _, _)| !found.contains(entry))
+                .map(|(entry, score, language)| {
+                    let score = score * fuzzy::SCORE_DESCRIPTION_MAX / fuzzy::SCORE_EXACT;
+                    Hit::from_index((entry, score, language), MatchField::Description)
+                }),
+        );
+    }
+    if let Some(ranges) = id_ranges(&text) {
+        let score = match ranges.as_slice() {
+            [range] if range.min.is_some() && range.min == range.max => fuzzy::SCORE_EXACT,
+            _ => fuzzy::SCORE_PREFIX,
+        };
+        let entries = index.by_id_ranges(&ranges);
+        let found: HashSet<usize> = entries.iter().copied().collect();
+        candidates.retain(|hit| !found.contains(&hit.entry));
+        candidates.extend(entries.into_iter().map(|entry| Hit {
+            entry,
+            score,
+            language: None,
+            field: Some(MatchField::Id),
+        }));
+    }
+
+    candidates.retain(|hit| {
+        let mut texts = index.names(hit.entry, &languages);
+        if descriptions {
+            texts.extend(index.descriptions(hit.entry, &languages));
+        }
+        group
+            .iter()
+            .all(|term| term.matches(&handbook[hit.entry], &texts))
+    });
+    (languages, candidates)
+}
+
+/// Results of `find`, summarized on request.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum FindResponse {
+    Full(SearchResponse),
+    Summary(SearchResponse<ItemSummary>),
+}
+
+/// Searches the handbooks selected by `handbook`: a registered name, a game
+/// (`gi`, `sr`) or `all`. Without it, the handbook loaded by
+/// `update_path_handbook` is searched.
+///
+/// With `summary`, results only hold the ID, name in one language, category,
+/// rarity and image; `get_item` returns the rest of an entry.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn find(
+    search: &str,
+    language: &str,
+    limit: Option<i64>,
+    offset: Option<usize>,
+    filter: Option<SearchFilter>,
+    descriptions: Option<bool>,
+    handbook: Option<String>,
+    summary: Option<bool>,
+) -> Result<FindResponse, String> {
+    let filter = filter.unwrap_or_default();
+    let search = search.trim();
+    if search.is_empty() && filter.is_empty() {
+        return Err(GmError::EmptySearchTerm.to_string());
+    }
+
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    let sources: Vec<SearchSource> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .map(SearchSource::from)
+        .collect();
+
+    let request = SearchRequest {
+        search,
+        language,
+        filter,
+        descriptions: descriptions.unwrap_or_default(),
+        offset: offset.unwrap_or_default(),
+        limit: limit.and_then(|limit| usize::try_from(limit).ok()),
+    };
+    let response = match summary.unwrap_or_default() {
+        true => summarize_handbooks(&sources, &request).map(FindResponse::Summary),
+        false => search_handbooks(&sources, &request).map(FindResponse::Full),
+    };
+    response.map_err(|e| e.to_string())
 }
 
+/// Handbooks selected by a command's `handbook` argument, failing when there
+/// are none.
+pub(crate) fn select_handbooks<'a>(
+    handbooks: &'a HandbookRegistry,
+    target: Option<&str>,
+) -> Result<Vec<&'a LoadedHandbook>, String> {
+    let selected = handbooks.select(&HandbookTarget::parse(target));
+    if selected.is_empty() {
+        let target = target.unwrap_or(DEFAULT_HANDBOOK).to_string();
+        return Err(GmError::HandbookNotFound(target).to_string());
+    }
+    Ok(selected)
+}
+
+/// Loads the handbook used when a command names no handbook. In strict mode
+/// a txt handbook with problems is refused instead of loaded with the broken
+/// lines skipped or defaulted.
 #[tauri::command(async)]
-pub fn update_path_handbook(path: &str, force: bool) -> Result<(), String> {
+pub fn update_path_handbook(
+    path: &str,
+    force: bool,
+    strict: Option<bool>,
+) -> Result<HandbookInfo, String> {
     if path.is_empty() {
         return Err(GmError::PathEmpty.to_string());
     }
@@ -92,66 +486,123 @@ pub fn update_path_handbook(path: &str, force: bool) -> Result<(), String> {
         warn!("Force updating handbook path to: {}", path);
     }
 
-    let path = Path::new(path);
-    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    let loaded = load_handbook(DEFAULT_HANDBOOK, path, None, strict.unwrap_or_default())?;
+    let info = loaded.info();
+    {
+        // The path changes under the registry lock, together with the
+        // handbook, so a reload of the previous path cannot replace it.
+        let mut handbooks = HANDBOOKS.write().map_err(|e| e.to_string())?;
+        handbooks.insert(loaded);
+        *HANDBOOK_PATH.write().map_err(|e| e.to_string())? = path.to_string();
+    }
+    watch::retarget();
 
-    let handbook_content = match extension {
-        "json" => parse_json_file(path),
-        "txt" => parse_txt_file(path),
-        _ => {
-            return Err(format!("Unsupported file format: {}", extension));
-        }
-    }?;
+    Ok(info)
+}
 
-    let mut handbook_content_lock = HANDBOOK_CONTENT.write().map_err(|e| e.to_string())?;
-    *handbook_content_lock = handbook_content;
-    *HANDBOOK_PATH.write().unwrap() = path.to_str().unwrap().to_string();
+/// Reloads the default handbook from `path`. The new handbook replaces the
+/// current one in a single step, after it was parsed, so a file that fails
+/// to parse leaves the current handbook in place. Returns `None` without
+/// replacing anything when another path was selected while it loaded.
+pub(crate) fn reload_default_handbook(
+    path: &str,
+    strict: bool,
+) -> Result<Option<HandbookInfo>, String> {
+    let loaded = load_handbook(DEFAULT_HANDBOOK, path, None, strict)?;
+    let info = loaded.info();
 
-    Ok(())
+    let mut handbooks = HANDBOOKS.write().map_err(|e| e.to_string())?;
+    // Checked under the registry lock, which `update_path_handbook` holds
+    // while changing the path.
+    if *HANDBOOK_PATH.read().map_err(|e| e.to_string())? != path {
+        return Ok(None);
+    }
+    handbooks.insert(loaded);
+    Ok(Some(info))
 }
 
-fn parse_json_file(path: &Path) -> Result<Gmhandbook, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
-    serde_json::from_reader(reader).map_err(|e| format!("Failed to parse JSON: {}", e))
+/// Loads a handbook next to the ones already loaded, so several handbooks,
+/// e.g. one per game, can be searched side by side. A handbook registered
+/// under the same name is replaced. The game is detected when not given.
+#[tauri::command(async)]
+pub fn register_handbook(
+    name: &str,
+    path: &str,
+    game: Option<String>,
+    strict: Option<bool>,
+) -> Result<HandbookInfo, String> {
+    let name = name.trim();
+    if HandbookTarget::parse(Some(name)) != HandbookTarget::Named(name.to_string()) {
+        return Err(GmError::ReservedHandbookName(name.to_string()).to_string());
+    }
+    if path.is_empty() {
+        return Err(GmError::PathEmpty.to_string());
+    }
+    let game = game.as_deref().map(Game::from_str).transpose()?;
+
+    let loaded = load_handbook(name, path, game, strict.unwrap_or_default())?;
+    let info = loaded.info();
+    info!(
+        "Registered handbook {} ({}) from {}",
+        name, loaded.game, path
+    );
+
+    HANDBOOKS.write().map_err(|e| e.to_string())?.insert(loaded);
+    Ok(info)
 }
 
-fn parse_txt_file(path: &Path) -> Result<Gmhandbook, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
-    let mut data = Vec::new();
-    let mut current_category = String::new();
-
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
-        if line.starts_with("# ") || line.starts_with("// ") {
-            current_category = if line.starts_with("// ") {
-                line.trim_start_matches("// ").to_string()
-            } else {
-                line.trim_start_matches("# ").to_string()
-            };
-        } else if !line.is_empty() {
-            let parts: Vec<&str> = line.splitn(2, ':').collect();
-            if parts.len() == 2 {
-                let id = parts[0].trim().parse().unwrap_or_default();
-                let name = parts[1].trim().to_string();
-                let category = Category::from_str(&current_category).unwrap_or(Category::Unknown);
-                data.push(GmhandbookElement {
-                    id,
-                    name: crate::structure::gm::NameUnion::String(name),
-                    category,
-                    commands: None,
-                    description: None,
-                    gmhandbook_type: None,
-                    icon: None,
-                    image: None,
-                    rarity: None,
-                })
-            }
+fn load_handbook(
+    name: &str,
+    path: &str,
+    game: Option<Game>,
+    strict: bool,
+) -> Result<LoadedHandbook, String> {
+    let data = cache::load(Path::new(path), load_handbook_file)?;
+    if let Some(report) = data.report.as_ref().filter(|report| !report.is_clean()) {
+        if strict {
+            return Err(GmError::InvalidHandbook(report.summary()).to_string());
         }
+        warn!("Loaded {} with {}", path, report.summary());
+    }
+
+    let game = game.unwrap_or_else(|| Game::detect(&data.content));
+    let mut loaded = LoadedHandbook::new(name, game, path, data.content, data.index);
+    loaded.report = data.report;
+    loaded.strict = strict;
+    Ok(loaded)
+}
+
+#[tauri::command]
+pub fn unregister_handbook(name: &str) -> Result<(), String> {
+    HANDBOOKS
+        .write()
+        .map_err(|e| e.to_string())?
+        .remove(name)
+        .map(|_| ())
+        .ok_or_else(|| GmError::HandbookNotFound(name.to_string()).to_string())
+}
+
+#[tauri::command]
+pub fn list_handbooks() -> Result<Vec<HandbookInfo>, String> {
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    Ok(handbooks.iter().map(LoadedHandbook::info).collect())
+}
+
+pub(crate) fn load_handbook_file(path: &Path) -> Result<(Gmhandbook, Option<ParseReport>), String> {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    match extension {
+        "json" => Ok((parse_json_file(path)?, None)),
+        "txt" => parse_txt_file(path).map(|(handbook, report)| (handbook, Some(report))),
+        _ => Err(format!("Unsupported file format: {}", extension)),
     }
+}
 
-    Ok(data)
+pub(crate) fn parse_json_file(path: &Path) -> Result<Gmhandbook, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let reader = BufReader::new(file);
+    serde_json::from_reader(reader).map_err(|e| format!("Failed to parse JSON: {}", e))
 }
 
 #[tauri::command]
@@ -161,12 +612,13 @@ pub fn get_path_handbook() -> Result<String, String> {
 }
 
 #[tauri::command(async)]
-pub fn get_category() -> Result<Vec<String>, String> {
+pub fn get_category(handbook: Option<String>) -> Result<Vec<String>, String> {
     info!("Attempting to read handbook content...");
-    let handbook_content = HANDBOOK_CONTENT.read().unwrap();
+    let handbooks = HANDBOOKS.read().unwrap();
     info!("Successfully read handbook content.");
-    let unique_categories: HashSet<String> = handbook_content
-        .iter()
+    let unique_categories: HashSet<String> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .flat_map(|loaded| loaded.content.iter())
         .map(|data| data.category.to_string())
         .collect();
     let mut result: Vec<String> = unique_categories.into_iter().collect();
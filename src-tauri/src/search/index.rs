@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bincode::{Decode, Encode};
+
+use crate::search::filter::NumberRange;
+use crate::search::fuzzy;
+use crate::search::normalize::{normalize, transliterate};
+use crate::structure::gm::{Gmhandbook, NameUnion};
+
+type Gram = [char; 3];
+
+/// Marker used to pad words so their first and last letters form trigrams too.
+const PAD: char = '\0';
+
+/// Inverted index over the names, or descriptions, of one language.
+#[derive(Default, Encode, Decode)]
+pub struct LanguageIndex {
+    /// Normalized name of every indexed entry, keyed by its position in the handbook.
+    names: HashMap<usize, String>,
+    /// Transliterated names, for entries whose name is not ASCII.
+    keys: HashMap<usize, String>,
+    /// Every distinct word, sorted so prefixes can be looked up with a range scan.
+    token_ids: BTreeMap<String, usize>,
+    /// Entries containing each word, indexed by word id.
+    postings: Vec<Vec<usize>>,
+    /// Words containing each padded trigram.
+    grams: HashMap<Gram, Vec<usize>>,
+    /// Every name, and every name from each of its words on, as entry and
+    /// byte offset in its name, sorted so prefixes can be completed with a
+    /// binary search. Only built for names.
+    prefixes: Vec<(usize, usize)>,
+}
+
+impl LanguageIndex {
+    fn insert(&mut self, entry: usize, name: &str) {
+        let name = normalize(name);
+        let key = transliterate(&name);
+        let tokens: HashSet<&str> = fuzzy::tokenize(&name)
+            .into_iter()
+            .chain(key.as_deref().map(fuzzy::tokenize).unwrap_or_default())
+            .collect();
+        for token in tokens {
+            let id = match self.token_ids.get(token) {
+                Some(&id) => id,
+                None => {
+                    let id = self.postings.len();
+                    self.token_ids.insert(token.to_string(), id);
+                    self.postings.push(Vec::new());
+                    for gram in grams(token, true) {
+                        self.grams.entry(gram).or_default().push(id);
+                    }
+                    id
+                }
+            };
+            self.postings[id].push(entry);
+        }
+        if let Some(key) = key {
+            self.keys.insert(entry, key);
+        }
+        self.names.insert(entry, name);
+    }
+
+    /// Whether every entry, word and name offset refers to something that
+    /// exists, for an index decoded from a cache of a handbook of `entries`.
+    fn is_consistent(&self, entries: usize) -> bool {
+        let words = self.postings.len();
+        self.names
+            .keys()
+            .chain(self.keys.keys())
+            .all(|&entry| entry < entries)
+            && self.token_ids.values().all(|&id| id < words)
+            && self.postings.iter().flatten().all(|&entry| entry < entries)
+            && self.grams.values().flatten().all(|&id| id < words)
+            && self.prefixes.iter().all(|&(entry, start)| {
+                self.names
+                    .get(&entry)
+                    .is_some_and(|name| name.is_char_boundary(start))
+            })
+    }
+
+    fn suffix(&self, (entry, start): (usize, usize)) -> &str {
+        &self.names[&entry][start..]
+    }
+
+    fn build_prefixes(&mut self) {
+        let mut prefixes = Vec::new();
+        for (&entry, name) in &self.names {
+            for token in fuzzy::tokenize(name) {
+                prefixes.push((entry, token.as_ptr() as usize - name.as_ptr() as usize));
+            }
+        }
+        prefixes.sort_unstable_by(|&a, &b| self.suffix(a).cmp(self.suffix(b)).then(a.cmp(&b)));
+        self.prefixes = prefixes;
+    }
+
+    /// Entries with a word starting with `prefix`, right after words equal to
+    /// `words`, and whether these words start the name.
+    fn complete<'a>(
+        &'a self,
+        prefix: &'a str,
+        words: &'a [&'a str],
+    ) -> impl Iterator<Item = (usize, bool)> + 'a {
+        let first = self
+            .prefixes
+            .partition_point(|&position| self.suffix(position) < prefix);
+        self.prefixes[first..]
+            .iter()
+            .take_while(move |&&position| self.suffix(position).starts_with(prefix))
+            .filter_map(move |&(entry, start)| {
+                let before = fuzzy::tokenize(&self.names[&entry][..start]);
+                before
+                    .ends_with(words)
+                    .then_some((entry, before.len() == words.len()))
+            })
+    }
+
+    /// Ids of the words that may match `query_token`: words starting with it,
+    /// containing it, or within typo distance of it.
+    fn matching_tokens(&self, query_token: &str) -> HashSet<usize> {
+        let mut found: HashSet<usize> = self
+            .token_ids
+            .range(query_token.to_string()..)
+            .take_while(|(token, _)| token.starts_with(query_token))
+            .map(|(_, &id)| id)
+            .collect();
+
+        let inner: HashSet<Gram> = grams(query_token, false).into_iter().collect();
+        let padded: HashSet<Gram> = grams(query_token, true).into_iter().collect();
+        let typos = fuzzy::max_typos(query_token.chars().count());
+        let needed = padded.len().saturating_sub(3 * typos).max(1);
+
+        // (inner trigrams hit, padded trigrams hit) per word
+        let mut hits: HashMap<usize, (usize, usize)> = HashMap::new();
+        for gram in &padded {
+            let is_inner = inner.contains(gram);
+            for &id in self.grams.get(gram).into_iter().flatten() {
+                let counter = hits.entry(id).or_default();
+                counter.1 += 1;
+                if is_inner {
+                    counter.0 += 1;
+                }
+            }
+        }
+        found.extend(
+            hits.into_iter()
+                .filter_map(|(id, (inner_hits, padded_hits))| {
+                    let contains = !inner.is_empty() && inner_hits == inner.len();
+                    let close = typos > 0 && padded_hits >= needed;
+                    (contains || close).then_some(id)
+                }),
+        );
+        found
+    }
+
+    /// Entries in which every word of the query matches some word of the name.
+    fn candidates(&self, query_tokens: &[&str]) -> HashSet<usize> {
+        let mut result: Option<HashSet<usize>> = None;
+        for query_token in query_tokens {
+            let entries: HashSet<usize> = self
+                .matching_tokens(query_token)
+                .into_iter()
+                .flat_map(|id| self.postings[id].iter().copied())
+                .collect();
+            let narrowed = match result {
+                Some(previous) => previous.intersection(&entries).copied().collect(),
+                None => entries,
+            };
+            if narrowed.is_empty() {
+                return narrowed;
+            }
+            result = Some(narrowed);
+        }
+        result.unwrap_or_default()
+    }
+
+    /// Scores every entry matching `query`, which must already be normalized.
+    ///
+    /// Transliterated names are searched too, both with the query as typed and
+    /// transliterated, but a match found only that way never ranks above
+    /// [`fuzzy::SCORE_EXACT_TOKENS`].
+    fn search(&self, query: &str) -> Vec<(usize, u32)> {
+        let query_key = transliterate(query);
+        let mut candidates = self.candidates(&fuzzy::tokenize(query));
+        if let Some(query_key) = &query_key {
+            candidates.extend(self.candidates(&fuzzy::tokenize(query_key)));
+        }
+        candidates
+            .into_iter()
+            .filter_map(|entry| {
+                let direct = self
+                    .names
+                    .get(&entry)
+                    .and_then(|name| fuzzy::score(query, name));
+                let key = self.keys.get(&entry).and_then(|key| {
+                    let typed = fuzzy::score(query, key);
+                    let transliterated = query_key
+                        .as_deref()
+                        .and_then(|query_key| fuzzy::score(query_key, key));
+                    Some(typed.max(transliterated)?.min(fuzzy::SCORE_EXACT_TOKENS))
+                });
+                Some((entry, direct.max(key)?))
+            })
+            .collect()
+    }
+}
+
+/// Search index built once when a handbook is loaded, so `find` never has to
+/// scan or lowercase the whole handbook.
+#[derive(Default, Encode, Decode)]
+pub struct HandbookIndex {
+    /// Names keyed by language code as found in the handbook (`EN`, `JP`, ...).
+    languages: HashMap<String, LanguageIndex>,
+    /// Entries whose name is a plain string, searchable from every language.
+    neutral: LanguageIndex,
+    /// Descriptions keyed by language code, only searched on request.
+    descriptions: HashMap<String, LanguageIndex>,
+    /// Entries by ID, sorted so ID ranges can be looked up with a range scan.
+    ids: BTreeMap<i64, Vec<usize>>,
+}
+
+impl HandbookIndex {
+    pub fn build(handbook: &Gmhandbook) -> Self {
+        let mut index = HandbookIndex::default();
+        for (entry, item) in handbook.iter().enumerate() {
+            index.ids.entry(item.id).or_default().push(entry);
+            match &item.name {
+                NameUnion::Description(names) => {
+                    for (language, name) in names {
+                        index
+                            .languages
+                            .entry(language.clone())
+                            .or_default()
+                            .insert(entry, name);
+                    }
+                }
+                NameUnion::String(name) => index.neutral.insert(entry, name),
+            }
+            for (language, description) in item.description.iter().flatten() {
+                index
+                    .descriptions
+                    .entry(language.clone())
+                    .or_default()
+                    .insert(entry, description);
+            }
+        }
+        for names in index.languages.values_mut() {
+            names.build_prefixes();
+        }
+        index.neutral.build_prefixes();
+        index
+    }
+
+    /// Normalized names of an entry in each of `languages`, followed by its
+    /// language-independent name.
+    pub fn names(&self, entry: usize, languages: &[&str]) -> Vec<&str> {
+        languages
+            .iter()
+            .filter_map(|language| self.languages.get(*language)?.names.get(&entry))
+            .chain(self.neutral.names.get(&entry))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Normalized descriptions of an entry in each of `languages`.
+    pub fn descriptions(&self, entry: usize, languages: &[&str]) -> Vec<&str> {
+        languages
+            .iter()
+            .filter_map(|language| self.descriptions.get(*language)?.names.get(&entry))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether the index only refers to entries of a handbook of `entries`,
+    /// so a corrupt or stale cache is rebuilt rather than used.
+    pub fn is_consistent(&self, entries: usize) -> bool {
+        self.languages
+            .values()
+            .chain(self.descriptions.values())
+            .chain([&self.neutral])
+            .all(|index| index.is_consistent(entries))
+            && self.ids.values().flatten().all(|&entry| entry < entries)
+    }
+
+    /// Entries with the given ID, in file order.
+    pub fn by_id(&self, id: i64) -> &[usize] {
+        self.ids.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Entries whose ID is in any of `ranges`, in ID order.
+    pub fn by_id_ranges(&self, ranges: &[NumberRange]) -> Vec<usize> {
+        let mut entries: Vec<(i64, usize)> = Vec::new();
+        for range in ranges {
+            let min = range.min.unwrap_or(i64::MIN);
+            let max = range.max.unwrap_or(i64::MAX);
+            if min > max {
+                continue;
+            }
+            for (&id, ids) in self.ids.range(min..=max) {
+                entries.extend(ids.iter().map(|&entry| (id, entry)));
+            }
+        }
+        entries.sort_unstable();
+        entries.dedup();
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Scores the entries whose name in any of `languages` matches `query`.
+    ///
+    /// `languages` are handbook language codes such as `EN`; entries with a
+    /// language-independent name are always searched. Each entry is returned
+    /// once with its best score and the language that produced it, `None`
+    /// meaning the language-independent name. On equal scores the language
+    /// listed first wins.
+    pub fn search<'l>(
+        &self,
+        query: &str,
+        languages: &[&'l str],
+    ) -> Vec<(usize, u32, Option<&'l str>)> {
+        search_languages(&self.languages, Some(&self.neutral), query, languages)
+    }
+
+    /// Entries whose name in any of `languages`, or language-independent
+    /// name, starts with `prefix` or has a word starting with it. The prefix is
+    /// tokenized as `search` does, its last word completing a word of the name
+    /// and the others matching the words right before it. Each entry comes
+    /// once, with the language of the first name found and whether the whole
+    /// name starts with `prefix`.
+    pub fn complete<'l>(
+        &self,
+        prefix: &str,
+        languages: &[&'l str],
+    ) -> Vec<(usize, Option<&'l str>, bool)> {
+        let prefix = normalize(prefix);
+        let tokens = fuzzy::tokenize(&prefix);
+        let Some((last, words)) = tokens.split_last() else {
+            return Vec::new();
+        };
+        let indexes = languages
+            .iter()
+            .filter_map(|&language| Some((Some(language), self.languages.get(language)?)))
+            .chain(std::iter::once((None, &self.neutral)));
+        let mut found: HashMap<usize, (Option<&'l str>, bool)> = HashMap::new();
+        for (language, index) in indexes {
+            for (entry, whole) in index.complete(last, words) {
+                let current = found.entry(entry).or_insert((language, whole));
+                if whole && !current.1 {
+                    *current = (language, whole);
+                }
+            }
+        }
+        found
+            .into_iter()
+            .map(|(entry, (language, whole))| (entry, language, whole))
+            .collect()
+    }
+
+    /// Same as [`HandbookIndex::search`], over descriptions instead of names.
+    pub fn search_descriptions<'l>(
+        &self,
+        query: &str,
+        languages: &[&'l str],
+    ) -> Vec<(usize, u32, Option<&'l str>)> {
+        search_languages(&self.descriptions, None, query, languages)
+    }
+}
+
+/// Best score and matching language of every entry matching `query` in
+/// `neutral` or in the indexes of `languages`.
+fn search_languages<'l>(
+    indexes: &HashMap<String, LanguageIndex>,
+    neutral: Option<&LanguageIndex>,
+    query: &str,
+    languages: &[&'l str],
+) -> Vec<(usize, u32, Option<&'l str>)> {
+    let query = normalize(query);
+    let mut best: HashMap<usize, (u32, Option<&'l str>)> = neutral
+        .map(|neutral| neutral.search(&query))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(entry, score)| (entry, (score, None)))
+        .collect();
+    for &language in languages {
+        let Some(language_index) = indexes.get(language) else {
+            continue;
+        };
+        for (entry, score) in language_index.search(&query) {
+            let current = best.entry(entry).or_insert((score, Some(language)));
+            if score > current.0 {
+                *current = (score, Some(language));
+            }
+        }
+    }
+    best.into_iter()
+        .map(|(entry, (score, language))| (entry, score, language))
+        .collect()
+}
+
+/// Trigrams of a word, optionally padded so the word boundaries count too.
+fn grams(token: &str, padded: bool) -> Vec<Gram> {
+    let mut chars: Vec<char> = token.chars().collect();
+    if padded {
+        chars.insert(0, PAD);
+        chars.push(PAD);
+    }
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
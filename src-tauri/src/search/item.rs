@@ -0,0 +1,96 @@
+use serde::Serialize;
+
+use crate::search::gi::{select_handbooks, GmError, SearchSource};
+use crate::search::registry::Game;
+use crate::structure::gm::{Category, GmhandbookElement, NameUnion};
+use crate::HANDBOOKS;
+
+/// What a result list shows of an entry, without its other languages,
+/// description and commands.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ItemSummary {
+    pub id: i64,
+    /// The name in the language that matched, or else the first searched
+    /// language the entry has a name in.
+    pub name: String,
+    pub category: Category,
+    pub rarity: Option<i64>,
+    pub image: Option<String>,
+}
+
+impl ItemSummary {
+    pub fn new(item: &GmhandbookElement, matched: Option<&str>, languages: &[&str]) -> Self {
+        let name = match &item.name {
+            NameUnion::String(name) => Some(name),
+            NameUnion::Description(names) => matched
+                .into_iter()
+                .chain(languages.iter().copied())
+                .find_map(|language| names.get(language))
+                // Keeps the name stable when no searched language has one.
+                .or_else(|| names.iter().min().map(|(_, name)| name)),
+        };
+        ItemSummary {
+            id: item.id,
+            name: name.cloned().unwrap_or_default(),
+            category: item.category.clone(),
+            rarity: item.rarity,
+            image: item.image.clone(),
+        }
+    }
+}
+
+/// A whole entry, with the handbook it comes from.
+#[derive(Serialize, Clone)]
+pub struct HandbookItem {
+    #[serde(flatten)]
+    pub item: GmhandbookElement,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handbook: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game: Option<Game>,
+}
+
+/// The first entry of `sources` with this category and ID. Categories are
+/// matched the way `find` filters them.
+pub fn find_item(
+    sources: &[SearchSource],
+    category: &Category,
+    id: i64,
+) -> Result<HandbookItem, GmError> {
+    sources
+        .iter()
+        .find_map(|source| {
+            let entry = source
+                .index
+                .by_id(id)
+                .iter()
+                .find(|&&entry| source.handbook[entry].category.same_listing(category))?;
+            Some(HandbookItem {
+                item: source.handbook[*entry].clone(),
+                handbook: source.name.map(str::to_string),
+                game: source.game,
+            })
+        })
+        .ok_or_else(|| GmError::ItemNotFound {
+            category: category.clone(),
+            id,
+        })
+}
+
+/// Returns an entry with every language, its description and its commands,
+/// e.g. for the result picked from a `find` summary.
+#[tauri::command]
+pub fn get_item(
+    category: Category,
+    id: i64,
+    handbook: Option<String>,
+) -> Result<HandbookItem, String> {
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    let sources: Vec<SearchSource> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .map(SearchSource::from)
+        .collect();
+    find_item(&sources, &category, id).map_err(|e| e.to_string())
+}
@@ -0,0 +1,104 @@
+use std::str::FromStr;
+
+use crate::structure::handbook::Language;
+
+/// Which handbook languages `find` searches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LanguageMode {
+    /// Only names in this language.
+    Single(Language),
+    /// Names in every language of the handbook.
+    All,
+    /// Names in the languages whose script matches the query, see [`detect`].
+    Auto,
+}
+
+impl FromStr for LanguageMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" | "*" => Ok(LanguageMode::All),
+            "auto" => Ok(LanguageMode::Auto),
+            _ => Language::from_str(s).map(LanguageMode::Single),
+        }
+    }
+}
+
+impl LanguageMode {
+    /// Handbook language keys to search for `text`, in order of preference.
+    pub fn keys(&self, text: &str) -> Vec<&'static str> {
+        let languages = match self {
+            LanguageMode::Single(language) => vec![language.clone()],
+            LanguageMode::All => Language::ALL.to_vec(),
+            LanguageMode::Auto => {
+                let detected = detect(text);
+                if detected.is_empty() {
+                    Language::ALL.to_vec()
+                } else {
+                    detected
+                }
+            }
+        };
+        languages.iter().map(Language::key).collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+    Kana,
+    Hangul,
+    Thai,
+    Cyrillic,
+    Han,
+    Latin,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c as u32 {
+        0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Some(Script::Kana),
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Some(Script::Hangul),
+        0x0E00..=0x0E7F => Some(Script::Thai),
+        0x0400..=0x052F => Some(Script::Cyrillic),
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Some(Script::Han),
+        _ if c.is_ascii_alphabetic() => Some(Script::Latin),
+        0x00C0..=0x024F | 0x1E00..=0x1EFF if c.is_alphabetic() => Some(Script::Latin),
+        _ => None,
+    }
+}
+
+/// Guesses the languages a query may be written in from its script.
+///
+/// Kana means Japanese even when mixed with kanji, and Han characters alone may
+/// be Chinese or Japanese. Returns an empty list when the query has no letters,
+/// e.g. when it is a number.
+pub fn detect(text: &str) -> Vec<Language> {
+    let scripts: Vec<Script> = text.chars().filter_map(script).collect();
+    let has = |wanted: Script| scripts.contains(&wanted);
+
+    if has(Script::Kana) {
+        vec![Language::JP]
+    } else if has(Script::Hangul) {
+        vec![Language::KR]
+    } else if has(Script::Thai) {
+        vec![Language::TH]
+    } else if has(Script::Cyrillic) {
+        vec![Language::RU]
+    } else if has(Script::Han) {
+        vec![Language::Chs, Language::Cht, Language::JP]
+    } else if has(Script::Latin) {
+        vec![
+            Language::EN,
+            Language::FR,
+            Language::ID,
+            Language::DE,
+            Language::ES,
+            Language::IT,
+            Language::PT,
+            Language::TR,
+            Language::VI,
+        ]
+    } else {
+        Vec::new()
+    }
+}
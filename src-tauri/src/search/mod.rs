@@ -1 +1,17 @@
+pub mod cache;
+pub mod diff;
+pub mod export;
+pub mod filter;
+pub mod fuzzy;
 pub mod gi;
+pub mod index;
+pub mod item;
+pub mod language;
+pub mod normalize;
+pub mod query;
+pub mod registry;
+pub mod resolve;
+pub mod snippet;
+pub mod suggest;
+pub mod txt;
+pub mod watch;
@@ -0,0 +1,91 @@
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+use unicode_normalization::UnicodeNormalization;
+
+/// Folds text so that spelling variants compare equal when searching.
+///
+/// Compatibility forms such as full-width letters and half-width kana are
+/// unified with NFKC, letters are lowercased and diacritics are removed from
+/// Latin letters only, so `"Ｆａｖｏｎｉｕｓ"`, `"Favonius"` and `"favónius"` all
+/// become `"favonius"`. Vietnamese is written in Latin letters, so its tone
+/// marks are removed too and a name typed without them still matches.
+///
+/// Marks on every other script are kept, whether the input is precomposed
+/// or not, since dakuten in Japanese, `й` in Russian or the vowel signs of
+/// Thai and Devanagari change the word.
+pub fn normalize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    normalize_chars(text, |_, c| result.push(c));
+    result
+}
+
+/// Same as [`normalize`], also returning for every char of the result the
+/// index of the char of `text` it comes from, so matches found in normalized
+/// text can be located in the original.
+pub fn normalize_with_offsets(text: &str) -> (String, Vec<usize>) {
+    let mut result = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len());
+    normalize_chars(text, |offset, c| {
+        result.push(c);
+        offsets.push(offset);
+    });
+    (result, offsets)
+}
+
+/// Normalizes `text` one letter at a time, with the combining marks that
+/// follow it, so every output char can be traced back to an input char.
+fn normalize_chars(text: &str, mut emit: impl FnMut(usize, char)) {
+    let mut chars = text.chars().enumerate().peekable();
+    let mut cluster = String::new();
+    while let Some((offset, c)) = chars.next() {
+        cluster.clear();
+        cluster.push(c);
+        while let Some((_, mark)) = chars.next_if(|&(_, mark)| is_combining_mark(mark)) {
+            cluster.push(mark);
+        }
+        let latin = is_latin(c);
+        for c in cluster.nfkc().flat_map(char::to_lowercase) {
+            // Marks left over from decomposed input.
+            if latin && is_combining_mark(c) {
+                continue;
+            }
+            fold(c, &mut |folded| emit(offset, folded));
+        }
+    }
+}
+
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}')
+}
+
+/// ASCII spelling of normalized text, used as a secondary search key.
+///
+/// Han characters become pinyin, so Traditional and Simplified spellings share
+/// a key, kana become romaji and other scripts are romanized. Returns `None`
+/// when the text is already ASCII.
+pub fn transliterate(normalized: &str) -> Option<String> {
+    if normalized.is_ascii() {
+        return None;
+    }
+    let key = deunicode::deunicode(normalized).to_lowercase();
+    (!key.trim().is_empty()).then_some(key)
+}
+
+fn fold(c: char, emit: &mut impl FnMut(char)) {
+    match c {
+        'ß' => "ss".chars().for_each(emit),
+        'æ' => "ae".chars().for_each(emit),
+        'œ' => "oe".chars().for_each(emit),
+        'ø' => emit('o'),
+        'đ' => emit('d'),
+        'ł' => emit('l'),
+        'ı' => emit('i'),
+        '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => {
+            decompose_canonical(c, |part| {
+                if !is_combining_mark(part) {
+                    emit(part);
+                }
+            });
+        }
+        _ => emit(c),
+    }
+}
@@ -0,0 +1,266 @@
+use std::str::FromStr;
+
+use crate::search::filter::NumberRange;
+use crate::search::fuzzy;
+use crate::search::normalize::normalize;
+use crate::structure::gm::{Category, GmhandbookElement, Type};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Text(String),
+    Phrase(String),
+    Category(Vec<Category>),
+    Rarity(NumberRange),
+    /// Matches IDs in any of the ranges.
+    Id(Vec<NumberRange>),
+    Type(Vec<Type>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub negated: bool,
+    pub predicate: Predicate,
+}
+
+/// A parsed handbook search query.
+///
+/// A query is a list of terms separated by whitespace. Terms are combined with
+/// AND; `OR` (or `|`) starts an alternative group. Supported terms:
+///
+/// * `wolf`, `amos bow` - free text, fuzzily matched against the name
+/// * `"wolf's"` - phrase that has to appear in the name as written
+/// * `cat:Weapons`, `cat:Weapons,Artifacts`, `cat:"Battle Stages"` - category
+/// * `rarity:5`, `rarity:>=4`, `rarity:<3`, `rarity:4..5` - rarity
+/// * `id:11501`, `id:1150*`, `id:11400-11599`, `id:>20000000` - ID
+/// * `type:World` - scene type
+/// * `name:wolf` - same as free text
+///
+/// Free text made of a single ID pattern, like `11501`, `1150*` or
+/// `11500-11599`, also finds entries by ID, see [`parse_ids`].
+///
+/// Any term can be negated with a leading `-`, e.g. `slime -large`.
+/// Unknown `field:value` terms are treated as free text. Entries match when
+/// they satisfy every term of at least one group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub groups: Vec<Vec<Term>>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut groups = Vec::new();
+        let mut current = Vec::new();
+        for raw in split_terms(input) {
+            if raw == "OR" || raw == "|" {
+                if !current.is_empty() {
+                    groups.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            let (negated, body) = match raw.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, raw.as_str()),
+            };
+            if let Some(predicate) = parse_predicate(body)? {
+                current.push(Term { negated, predicate });
+            }
+        }
+        if !current.is_empty() || groups.is_empty() {
+            groups.push(current);
+        }
+        Ok(Query { groups })
+    }
+}
+
+impl Term {
+    /// Whether `item`, whose normalized names (and descriptions, when they are
+    /// searched) in the searched languages are `texts`, satisfies this term.
+    /// Text terms match if any of the texts does.
+    ///
+    /// Positive free text is not checked here: it is used to look candidates
+    /// up in the index and scored there.
+    pub fn matches(&self, item: &GmhandbookElement, texts: &[&str]) -> bool {
+        let matched = match &self.predicate {
+            Predicate::Text(text) if self.negated => texts
+                .iter()
+                .any(|value| fuzzy::score(text, value).is_some()),
+            Predicate::Text(_) => true,
+            Predicate::Phrase(phrase) => texts.iter().any(|text| text.contains(phrase.as_str())),
+            Predicate::Category(categories) => categories
+                .iter()
+                .any(|category| category.as_str() == item.category.as_str()),
+            Predicate::Rarity(range) => item.rarity.is_some_and(|rarity| range.contains(rarity)),
+            Predicate::Id(ranges) => ranges.iter().any(|range| range.contains(item.id)),
+            Predicate::Type(types) => item
+                .gmhandbook_type
+                .as_ref()
+                .is_some_and(|value| types.contains(value)),
+        };
+        matched != self.negated
+    }
+}
+
+/// Splits the input on whitespace, keeping quoted sections together.
+fn split_terms(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        if c == '"' {
+            quoted = !quoted;
+            current.push(c);
+        } else if c.is_whitespace() && !quoted {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.strip_prefix('"').unwrap_or(value);
+    value.strip_suffix('"').unwrap_or(value)
+}
+
+fn parse_predicate(term: &str) -> Result<Option<Predicate>, String> {
+    if term.starts_with('"') {
+        let phrase = normalize(unquote(term).trim());
+        return Ok((!phrase.is_empty()).then_some(Predicate::Phrase(phrase)));
+    }
+
+    if let Some((field, value)) = term.split_once(':') {
+        let value = &value.replace('"', "");
+        let predicate = match field.to_lowercase().as_str() {
+            "cat" | "category" => Some(Predicate::Category(parse_list(value, parse_category)?)),
+            "rarity" | "star" | "stars" => Some(Predicate::Rarity(parse_range(value)?)),
+            "id" => Some(Predicate::Id(parse_ids(value)?)),
+            "type" => Some(Predicate::Type(parse_list(value, |v| {
+                Type::from_str(v).map_err(|_| format!("Unknown scene type: {}", v))
+            })?)),
+            "name" => Some(Predicate::Text(normalize(value))),
+            _ => None,
+        };
+        if predicate.is_some() {
+            return Ok(predicate);
+        }
+    }
+
+    Ok(Some(Predicate::Text(normalize(term))))
+}
+
+fn parse_list<T>(value: &str, parse: impl Fn(&str) -> Result<T, String>) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(parse)
+        .collect()
+}
+
+fn parse_category(value: &str) -> Result<Category, String> {
+    Category::from_str(value)
+        .ok()
+        .or_else(|| {
+            Category::ALL
+                .into_iter()
+                .find(|category| category.as_str().eq_ignore_ascii_case(value))
+        })
+        .ok_or_else(|| format!("Unknown category: {}", value))
+}
+
+/// Parses an ID pattern into the ranges of IDs it matches.
+///
+/// Accepts everything [`parse_range`] does, plus prefixes such as `1150*`,
+/// matching 1150, 11500 to 11509, 115000 to 115099 and so on, and ranges
+/// written `11500-11599`.
+pub fn parse_ids(value: &str) -> Result<Vec<NumberRange>, String> {
+    if let Some(prefix) = value.strip_suffix('*') {
+        if prefix.is_empty() || !prefix.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid ID prefix: {}", value));
+        }
+        let base: i64 = prefix
+            .parse()
+            .map_err(|_| format!("Invalid ID prefix: {}", value))?;
+        let mut ranges = Vec::new();
+        let mut scale: i64 = 1;
+        while let Some(min) = base.checked_mul(scale) {
+            let Some(max) = min.checked_add(scale - 1) else {
+                break;
+            };
+            ranges.push(NumberRange {
+                min: Some(min),
+                max: Some(max),
+            });
+            let Some(next) = scale.checked_mul(10) else {
+                break;
+            };
+            scale = next;
+        }
+        return Ok(ranges);
+    }
+    if let Some((min, max)) = value.split_once('-') {
+        if let (Ok(min), Ok(max)) = (min.trim().parse(), max.trim().parse()) {
+            return Ok(vec![NumberRange {
+                min: Some(min),
+                max: Some(max),
+            }]);
+        }
+    }
+    parse_range(value).map(|range| vec![range])
+}
+
+/// Parses `5`, `>=4`, `>4`, `<=3`, `<3`, `=5` or `4..5` into inclusive bounds.
+fn parse_range(value: &str) -> Result<NumberRange, String> {
+    let number = |v: &str| {
+        v.trim()
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid number: {}", v))
+    };
+    let range = if let Some((min, max)) = value.split_once("..") {
+        NumberRange {
+            min: (!min.is_empty()).then(|| number(min)).transpose()?,
+            max: (!max.is_empty()).then(|| number(max)).transpose()?,
+        }
+    } else if let Some(v) = value.strip_prefix(">=") {
+        NumberRange {
+            min: Some(number(v)?),
+            max: None,
+        }
+    } else if let Some(v) = value.strip_prefix("<=") {
+        NumberRange {
+            min: None,
+            max: Some(number(v)?),
+        }
+    } else if let Some(v) = value.strip_prefix('>') {
+        NumberRange {
+            min: Some(
+                number(v)?
+                    .checked_add(1)
+                    .ok_or_else(|| format!("Number out of range: {}", value))?,
+            ),
+            max: None,
+        }
+    } else if let Some(v) = value.strip_prefix('<') {
+        NumberRange {
+            min: None,
+            max: Some(
+                number(v)?
+                    .checked_sub(1)
+                    .ok_or_else(|| format!("Number out of range: {}", value))?,
+            ),
+        }
+    } else {
+        let v = number(value.strip_prefix('=').unwrap_or(value))?;
+        NumberRange {
+            min: Some(v),
+            max: Some(v),
+        }
+    };
+    Ok(range)
+}
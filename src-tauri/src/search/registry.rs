@@ -0,0 +1,192 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::index::HandbookIndex;
+use crate::search::txt::ParseReport;
+use crate::structure::gm::{Category, Gmhandbook};
+
+/// Name of the handbook loaded through `update_path_handbook`.
+pub const DEFAULT_HANDBOOK: &str = "default";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Game {
+    #[serde(rename = "Genshin Impact")]
+    GenshinImpact,
+    #[serde(rename = "Star Rail")]
+    StarRail,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Game::GenshinImpact => write!(f, "Genshin Impact"),
+            Game::StarRail => write!(f, "Star Rail"),
+        }
+    }
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gi" | "genshin" | "genshin impact" => Ok(Game::GenshinImpact),
+            "sr" | "hsr" | "star rail" | "honkai: star rail" => Ok(Game::StarRail),
+            _ => Err(format!("Unknown game: {}", s)),
+        }
+    }
+}
+
+impl Game {
+    /// Guesses the game of a handbook from its categories, which differ between
+    /// the GC and LunarCore handbook formats.
+    pub fn detect(handbook: &Gmhandbook) -> Self {
+        let star_rail = handbook.iter().any(|item| {
+            matches!(
+                item.category,
+                Category::Avatars
+                    | Category::Items
+                    | Category::PropsLC
+                    | Category::NPCMonstersLC
+                    | Category::BattleStages
+                    | Category::BattleMonsters
+                    | Category::Mazes
+                    | Category::Gadgets
+                    | Category::LightCones
+                    | Category::Relics
+            )
+        });
+        if star_rail {
+            Game::StarRail
+        } else {
+            Game::GenshinImpact
+        }
+    }
+}
+
+/// A handbook loaded from a file, with its search index.
+pub struct LoadedHandbook {
+    pub name: String,
+    pub game: Game,
+    pub path: String,
+    pub content: Gmhandbook,
+    pub index: HandbookIndex,
+    /// Problems found in a txt handbook, `None` for JSON handbooks.
+    pub report: Option<ParseReport>,
+    /// Whether the handbook was loaded in strict mode, which reloads keep.
+    pub strict: bool,
+}
+
+impl LoadedHandbook {
+    /// `index` is built with [`HandbookIndex::build`] or read from the handbook
+    /// cache along with `content`.
+    pub fn new(
+        name: &str,
+        game: Game,
+        path: &str,
+        content: Gmhandbook,
+        index: HandbookIndex,
+    ) -> Self {
+        LoadedHandbook {
+            name: name.to_string(),
+            game,
+            path: path.to_string(),
+            content,
+            index,
+            report: None,
+            strict: false,
+        }
+    }
+
+    pub fn info(&self) -> HandbookInfo {
+        HandbookInfo {
+            name: self.name.clone(),
+            game: self.game,
+            path: self.path.clone(),
+            entries: self.content.len(),
+            report: self.report.clone(),
+        }
+    }
+}
+
+/// Summary of a registered handbook sent to the UI.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct HandbookInfo {
+    pub name: String,
+    pub game: Game,
+    pub path: String,
+    pub entries: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<ParseReport>,
+}
+
+/// Which registered handbooks a command works on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandbookTarget {
+    /// The handbook with this name.
+    Named(String),
+    /// Every handbook of a game.
+    Game(Game),
+    /// Every registered handbook.
+    All,
+}
+
+impl HandbookTarget {
+    /// Parses `all`, a game such as `gi` or `sr`, or a handbook name. `None`
+    /// targets the handbook loaded through `update_path_handbook`.
+    pub fn parse(target: Option<&str>) -> Self {
+        match target.map(str::trim) {
+            None | Some("") => HandbookTarget::Named(DEFAULT_HANDBOOK.to_string()),
+            Some(target) if target.eq_ignore_ascii_case("all") || target == "*" => {
+                HandbookTarget::All
+            }
+            Some(target) => match Game::from_str(target) {
+                Ok(game) => HandbookTarget::Game(game),
+                Err(_) => HandbookTarget::Named(target.to_string()),
+            },
+        }
+    }
+}
+
+/// Handbooks loaded at the same time, in registration order.
+#[derive(Default)]
+pub struct HandbookRegistry {
+    handbooks: Vec<LoadedHandbook>,
+}
+
+impl HandbookRegistry {
+    /// Adds a handbook, replacing any handbook registered under the same name.
+    pub fn insert(&mut self, handbook: LoadedHandbook) {
+        match self.handbooks.iter_mut().find(|h| h.name == handbook.name) {
+            Some(existing) => *existing = handbook,
+            None => self.handbooks.push(handbook),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<LoadedHandbook> {
+        let position = self.handbooks.iter().position(|h| h.name == name)?;
+        Some(self.handbooks.remove(position))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LoadedHandbook> {
+        self.handbooks.iter().find(|h| h.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LoadedHandbook> {
+        self.handbooks.iter()
+    }
+
+    /// Handbooks selected by `target`, in registration order.
+    pub fn select(&self, target: &HandbookTarget) -> Vec<&LoadedHandbook> {
+        self.handbooks
+            .iter()
+            .filter(|handbook| match target {
+                HandbookTarget::Named(name) => handbook.name == *name,
+                HandbookTarget::Game(game) => handbook.game == *game,
+                HandbookTarget::All => true,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::search::gi::{select_handbooks, GmError, SearchSource};
+use crate::search::item::ItemSummary;
+use crate::search::language::LanguageMode;
+use crate::structure::gm::Category;
+use crate::HANDBOOKS;
+
+/// An ID to resolve, restricted to a category when given.
+#[derive(Deserialize, Clone, Debug)]
+pub struct IdQuery {
+    pub id: i64,
+    pub category: Option<Category>,
+}
+
+/// An entry an ID resolved to.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Candidate {
+    #[serde(flatten)]
+    pub item: ItemSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handbook: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Resolution {
+    Found(Candidate),
+    /// The ID is used in several categories, one candidate per category.
+    Ambiguous {
+        candidates: Vec<Candidate>,
+    },
+    Unknown,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ResolvedId {
+    pub id: i64,
+    /// The category the ID was restricted to.
+    pub category: Option<Category>,
+    #[serde(flatten)]
+    pub resolution: Resolution,
+}
+
+/// Resolves every ID of `ids`, in order. An ID found in the same category of
+/// several handbooks resolves to the first of `sources`.
+pub fn resolve(
+    sources: &[SearchSource],
+    ids: &[IdQuery],
+    language: &str,
+) -> Result<Vec<ResolvedId>, GmError> {
+    let languages = LanguageMode::from_str(language)
+        .map_err(|_| GmError::UnsupportedLanguage(language.to_string()))?
+        .keys("");
+
+    Ok(ids
+        .iter()
+        .map(|query| {
+            let mut candidates: Vec<Candidate> = Vec::new();
+            for source in sources {
+                for &entry in source.index.by_id(query.id) {
+                    let item = &source.handbook[entry];
+                    let wanted = query
+                        .category
+                        .as_ref()
+                        .is_none_or(|category| category.same_listing(&item.category));
+                    let known = candidates
+                        .iter()
+                        .any(|candidate| candidate.item.category.same_listing(&item.category));
+                    if wanted && !known {
+                        candidates.push(Candidate {
+                            item: ItemSummary::new(item, None, &languages),
+                            handbook: source.name.map(str::to_string),
+                        });
+                    }
+                }
+            }
+            let resolution = match candidates.len() {
+                0 => Resolution::Unknown,
+                1 => Resolution::Found(candidates.remove(0)),
+                _ => Resolution::Ambiguous { candidates },
+            };
+            ResolvedId {
+                id: query.id,
+                category: query.category.clone(),
+                resolution,
+            }
+        })
+        .collect())
+}
+
+/// Names the IDs of e.g. an inventory dump or a server log, with names in
+/// `language`, or the first language with a name for `all`.
+#[tauri::command]
+pub fn resolve_ids(
+    ids: Vec<IdQuery>,
+    language: &str,
+    handbook: Option<String>,
+) -> Result<Vec<ResolvedId>, String> {
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    let sources: Vec<SearchSource> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .map(SearchSource::from)
+        .collect();
+    resolve(&sources, &ids, language).map_err(|e| e.to_string())
+}
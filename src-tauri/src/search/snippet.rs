@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+use crate::search::fuzzy;
+use crate::search::normalize::normalize_with_offsets;
+
+/// Characters of context kept before the first highlight.
+const CONTEXT: usize = 40;
+/// Maximum length of a snippet, ellipses excluded.
+const LENGTH: usize = 160;
+const ELLIPSIS: char = '…';
+
+/// A highlighted range of a snippet, in UTF-16 code units as JavaScript
+/// indexes strings, end excluded.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Highlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Excerpt of a matching text with the matching words highlighted.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Snippet {
+    pub text: String,
+    pub highlights: Vec<Highlight>,
+}
+
+impl Snippet {
+    /// Builds the excerpt of `text` around the first word matching one of
+    /// `words`, or containing one of `phrases`. Both must already be normalized.
+    ///
+    /// When nothing can be highlighted, e.g. because the text only matched
+    /// through its transliteration, the start of the text is returned.
+    pub fn new(text: &str, words: &[&str], phrases: &[&str]) -> Self {
+        let (normalized, offsets) = normalize_with_offsets(text);
+        let mut spans = Vec::new();
+        // Byte range in `normalized` to char range in `text`.
+        let mut push = |from: usize, to: usize| {
+            let start = normalized[..from].chars().count();
+            let end = start + normalized[from..to].chars().count();
+            if end > start {
+                spans.push((offsets[start], offsets[end - 1] + 1));
+            }
+        };
+        for phrase in phrases.iter().filter(|phrase| !phrase.is_empty()) {
+            for (start, matched) in normalized.match_indices(phrase) {
+                push(start, start + matched.len());
+            }
+        }
+        for token in fuzzy::tokenize(&normalized) {
+            if words
+                .iter()
+                .any(|word| fuzzy::token_score(word, token).is_some())
+            {
+                let start = token.as_ptr() as usize - normalized.as_ptr() as usize;
+                push(start, start + token.len());
+            }
+        }
+        spans.sort_unstable();
+
+        let chars: Vec<char> = text.chars().collect();
+        let first = spans.first().map_or((0, 0), |&span| span);
+        let window_start = first.0.saturating_sub(CONTEXT);
+        let window_end = chars.len().min((window_start + LENGTH).max(first.1));
+
+        let mut snippet = String::new();
+        let shift = if window_start > 0 {
+            snippet.push(ELLIPSIS);
+            1
+        } else {
+            0
+        };
+        snippet.extend(&chars[window_start..window_end]);
+        if window_end < chars.len() {
+            snippet.push(ELLIPSIS);
+        }
+
+        let mut highlights: Vec<Highlight> = Vec::new();
+        for (start, end) in spans {
+            if start >= window_end {
+                break;
+            }
+            let start = start.max(window_start) - window_start + shift;
+            let end = end.min(window_end) - window_start + shift;
+            match highlights.last_mut() {
+                Some(last) if start <= last.end => last.end = last.end.max(end),
+                _ => highlights.push(Highlight { start, end }),
+            }
+        }
+        // Char offsets to UTF-16 offsets, which differ past astral-plane
+        // characters such as emoji.
+        let units: Vec<usize> = std::iter::once(0)
+            .chain(snippet.chars().scan(0, |offset, c| {
+                *offset += c.len_utf16();
+                Some(*offset)
+            }))
+            .collect();
+        for highlight in &mut highlights {
+            highlight.start = units[highlight.start];
+            highlight.end = units[highlight.end];
+        }
+        Snippet {
+            text: snippet,
+            highlights,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::cmp::Reverse;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::search::gi::{select_handbooks, GmError, SearchSource};
+use crate::search::language::LanguageMode;
+use crate::structure::gm::{Category, NameUnion};
+use crate::HANDBOOKS;
+
+/// Number of suggestions returned when no limit is given.
+pub const DEFAULT_SUGGESTIONS: usize = 10;
+
+/// A name completing the typed prefix.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub id: i64,
+    /// The name in the language it was found in.
+    pub name: String,
+    pub category: Category,
+    pub rarity: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handbook: Option<String>,
+}
+
+pub struct SuggestRequest<'a> {
+    pub prefix: &'a str,
+    /// Same as [`crate::search::gi::SearchRequest::language`].
+    pub language: &'a str,
+    pub category: Option<Category>,
+    pub limit: usize,
+}
+
+/// Names starting with the prefix, or with a word starting with it, from the
+/// prefix index built with the handbook. Names starting with the prefix come
+/// first, then higher rarities and shorter names.
+pub fn suggest_names(
+    sources: &[SearchSource],
+    request: &SuggestRequest,
+) -> Result<Vec<Suggestion>, GmError> {
+    let mode = LanguageMode::from_str(request.language)
+        .map_err(|_| GmError::UnsupportedLanguage(request.language.to_string()))?;
+    if request.prefix.trim().is_empty() || request.limit == 0 {
+        return Ok(Vec::new());
+    }
+    let languages = mode.keys(request.prefix);
+
+    let mut candidates = Vec::new();
+    for (position, source) in sources.iter().enumerate() {
+        for (entry, language, whole) in source.index.complete(request.prefix, &languages) {
+            let item = &source.handbook[entry];
+            if request
+                .category
+                .as_ref()
+                .is_some_and(|category| !category.same_listing(&item.category))
+            {
+                continue;
+            }
+            let name = match (&item.name, language) {
+                (NameUnion::Description(names), Some(language)) => names.get(language),
+                (NameUnion::String(name), _) => Some(name),
+                _ => None,
+            };
+            let Some(name) = name else {
+                continue;
+            };
+            let rank = (
+                Reverse(whole),
+                Reverse(item.rarity.unwrap_or_default()),
+                name.chars().count(),
+                position,
+                item.id,
+            );
+            candidates.push((rank, position, entry, name));
+        }
+    }
+
+    if candidates.len() > request.limit {
+        candidates.select_nth_unstable_by_key(request.limit - 1, |candidate| candidate.0);
+        candidates.truncate(request.limit);
+    }
+    candidates.sort_unstable_by_key(|candidate| candidate.0);
+
+    Ok(candidates
+        .into_iter()
+        .map(|(_, position, entry, name)| {
+            let item = &sources[position].handbook[entry];
+            Suggestion {
+                id: item.id,
+                name: name.clone(),
+                category: item.category.clone(),
+                rarity: item.rarity,
+                handbook: sources[position].name.map(str::to_string),
+            }
+        })
+        .collect())
+}
+
+/// Completes a name typed in a command builder field. Much cheaper than
+/// `find`, so it can run on every keystroke.
+#[tauri::command]
+pub fn suggest(
+    prefix: &str,
+    language: &str,
+    category: Option<Category>,
+    limit: Option<usize>,
+    handbook: Option<String>,
+) -> Result<Vec<Suggestion>, String> {
+    let handbooks = HANDBOOKS
+        .read()
+        .map_err(|_| GmError::ReadHandbookContentError.to_string())?;
+    let sources: Vec<SearchSource> = select_handbooks(&handbooks, handbook.as_deref())?
+        .into_iter()
+        .map(SearchSource::from)
+        .collect();
+    let request = SuggestRequest {
+        prefix,
+        language,
+        category,
+        limit: limit.unwrap_or(DEFAULT_SUGGESTIONS),
+    };
+    suggest_names(&sources, &request).map_err(|e| e.to_string())
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use bincode::{Decode, Encode};
+use serde::Serialize;
+
+use crate::structure::gm::{Category, Gmhandbook, GmhandbookElement, NameUnion, Type};
+use crate::structure::handbook::Language;
+
+/// Problem found on a line of a txt handbook.
+#[derive(Serialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TxtProblem {
+    /// The line is neither a section header nor an `id: name` entry, so it
+    /// was skipped.
+    MissingSeparator,
+    /// The ID is not a number, so the entry got the ID 0.
+    InvalidId { id: String },
+    /// The section header is not a known category, so its entries got the
+    /// `Unknown` category.
+    UnknownCategory { category: String },
+    /// The entry comes before any section header, so it got the `Unknown`
+    /// category.
+    MissingCategory,
+}
+
+impl fmt::Display for TxtProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxtProblem::MissingSeparator => write!(f, "expected `id: name`"),
+            TxtProblem::InvalidId { id } => write!(f, "invalid ID `{}`", id),
+            TxtProblem::UnknownCategory { category } => {
+                write!(f, "unknown category `{}`", category)
+            }
+            TxtProblem::MissingCategory => write!(f, "entry before any category"),
+        }
+    }
+}
+
+#[derive(Serialize, Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct TxtDiagnostic {
+    /// 1-based line number.
+    pub line: usize,
+    pub problem: TxtProblem,
+}
+
+impl fmt::Display for TxtDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.problem)
+    }
+}
+
+/// What went wrong while reading a txt handbook.
+#[derive(Serialize, Encode, Decode, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// Entries loaded, including the defaulted ones.
+    pub entries: usize,
+    /// Lines skipped because they could not be read as an entry.
+    pub dropped: usize,
+    /// Entries loaded with a default ID or category.
+    pub defaulted: usize,
+    pub diagnostics: Vec<TxtDiagnostic>,
+}
+
+impl ParseReport {
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// One-line description of the problems, e.g. for an error message.
+    pub fn summary(&self) -> String {
+        match self.diagnostics.first() {
+            None => "no problems".to_string(),
+            Some(first) => format!(
+                "{} problem(s), {} line(s) dropped, {} entry(ies) defaulted, first at {}",
+                self.diagnostics.len(),
+                self.dropped,
+                self.defaulted,
+                first
+            ),
+        }
+    }
+
+    fn report(&mut self, line: usize, problem: TxtProblem) {
+        self.diagnostics.push(TxtDiagnostic { line, problem });
+    }
+}
+
+/// Server whose handbook format a txt file follows, which decides what its
+/// sections mean.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Generator {
+    /// Grasscutter, with `// Section` headers and Genshin Impact entries.
+    Grasscutter,
+    /// LunarCore, with `# Section` headers and Star Rail entries.
+    LunarCore,
+}
+
+impl Generator {
+    /// Recognizes the title line both servers start their handbook with, e.g.
+    /// `// Grasscutter 1.7.4 GM Handbook`.
+    fn from_title(header: &str) -> Option<Self> {
+        let header = header.to_lowercase();
+        if header.contains("grasscutter") {
+            Some(Generator::Grasscutter)
+        } else if header.contains("lunar core") || header.contains("lunarcore") {
+            Some(Generator::LunarCore)
+        } else {
+            None
+        }
+    }
+
+    fn section(self, name: &str) -> Section {
+        match (self, name) {
+            (_, "Commands") => Section::Ignored,
+            (Generator::Grasscutter, "Avatars") => Section::Entries(Category::Characters),
+            (Generator::Grasscutter, "Items") => Section::GenshinItems,
+            _ => match Category::from_str(name) {
+                Ok(category) => Section::Entries(category),
+                Err(_) if name == Category::Unknown.as_str() => Section::Entries(Category::Unknown),
+                Err(_) => Section::Unknown(name.to_string()),
+            },
+        }
+    }
+}
+
+enum Section {
+    Entries(Category),
+    /// Grasscutter lists weapons, artifacts and materials together, so their
+    /// category comes from the ID.
+    GenshinItems,
+    /// Sections without handbook entries, such as the list of commands.
+    Ignored,
+    /// Title lines also look like sections, so an unknown section is only
+    /// reported once it has entries.
+    Unknown(String),
+}
+
+fn genshin_item_category(id: i64) -> Category {
+    match id {
+        10000..=19999 => Category::Weapons,
+        20000..=99999 => Category::Artifacts,
+        _ => Category::Materials,
+    }
+}
+
+/// Language of a handbook named after it, either as Grasscutter names them,
+/// `GM Handbook - EN.txt`, or as `<name>_<LANG>.txt`. The code has to be
+/// uppercase, so words such as `id` or `it` ending a file name are not taken
+/// for a language, and names stay language-independent.
+pub fn language_from_file_name(path: &Path) -> Option<Language> {
+    let stem = path.file_stem()?.to_str()?;
+    let (_, code) = stem.rsplit_once(" - ").or_else(|| stem.rsplit_once('_'))?;
+    if code.is_empty() || !code.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    Language::from_str(code).ok()
+}
+
+/// Reads a txt handbook, with names in the language its file name ends with
+/// or language-independent names otherwise.
+pub fn parse_txt_file(path: &Path) -> Result<(Gmhandbook, ParseReport), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    parse_txt(BufReader::new(file), language_from_file_name(path))
+}
+
+/// Reads `# Category` or `// Category` section headers followed by
+/// `id: name` lines, including the handbooks generated by Grasscutter and
+/// LunarCore. Columns after the name, separated by ` : `, are used when they
+/// are a scene type and ignored otherwise.
+///
+/// Lines that cannot be read are reported rather than failing the whole
+/// file.
+pub fn parse_txt(
+    reader: impl BufRead,
+    language: Option<Language>,
+) -> Result<(Gmhandbook, ParseReport), String> {
+    let mut data = Vec::new();
+    let mut report = ParseReport::default();
+    let mut generator = None;
+    let mut current_section = None;
+    let mut unknown_reported = false;
+
+    for (number, line) in reader.lines().enumerate() {
+        let number = number + 1;
+        let line = line.map_err(|e| format!("Failed to read line {}: {}", number, e))?;
+        let header = line
+            .strip_prefix("// ")
+            .map(|header| (header, Generator::Grasscutter))
+            .or_else(|| {
+                line.strip_prefix("# ")
+                    .map(|header| (header, Generator::LunarCore))
+            });
+        if let Some((header, style)) = header {
+            let header = header.trim();
+            if let Some(title) = Generator::from_title(header) {
+                generator = Some(title);
+            }
+            let section = generator.unwrap_or(style).section(header);
+            current_section = Some((number, section));
+            unknown_reported = false;
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        if matches!(current_section, Some((_, Section::Ignored))) {
+            continue;
+        }
+
+        let Some((id, rest)) = line.split_once(':') else {
+            report.dropped += 1;
+            report.report(number, TxtProblem::MissingSeparator);
+            continue;
+        };
+        let mut columns = rest.split(" : ");
+        let name = columns.next().unwrap_or_default().trim();
+        let scene_type = columns.find_map(|column| Type::from_str(column.trim()).ok());
+
+        let mut defaulted = false;
+        let id = id.trim().parse().unwrap_or_else(|_| {
+            defaulted = true;
+            let id = id.trim().to_string();
+            report.report(number, TxtProblem::InvalidId { id });
+            0
+        });
+        let category = match &current_section {
+            Some((_, Section::Entries(category))) => category.clone(),
+            Some((_, Section::GenshinItems)) => genshin_item_category(id),
+            Some((_, Section::Ignored)) => unreachable!("ignored sections are skipped"),
+            Some((header, Section::Unknown(category))) => {
+                if !unknown_reported {
+                    unknown_reported = true;
+                    let category = category.clone();
+                    report.report(*header, TxtProblem::UnknownCategory { category });
+                }
+                defaulted = true;
+                Category::Unknown
+            }
+            None => {
+                defaulted = true;
+                report.report(number, TxtProblem::MissingCategory);
+                Category::Unknown
+            }
+        };
+        if defaulted {
+            report.defaulted += 1;
+        }
+        let name = match &language {
+            Some(language) => NameUnion::Description(HashMap::from([(
+                language.key().to_string(),
+                name.to_string(),
+            )])),
+            None => NameUnion::String(name.to_string()),
+        };
+        data.push(GmhandbookElement {
+            id,
+            name,
+            category,
+            commands: None,
+            description: None,
+            gmhandbook_type: scene_type,
+            icon: None,
+            image: None,
+            rarity: None,
+        })
+    }
+
+    report.entries = data.len();
+    Ok((data, report))
+}
@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use log::{error, info, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::search::gi::{reload_default_handbook, GmError};
+use crate::search::registry::DEFAULT_HANDBOOK;
+use crate::search::txt::ParseReport;
+use crate::{HANDBOOKS, HANDBOOK_PATH, HANDBOOK_WATCHER};
+
+/// Event emitted with a [`HandbookReload`] every time the watched handbook
+/// is reloaded.
+pub const RELOAD_EVENT: &str = "handbook-reloaded";
+
+/// How long a file has to stay unchanged before it is reloaded, so a file
+/// written in several steps is only read once complete.
+const SETTLE: Duration = Duration::from_millis(300);
+
+/// Result of reloading the handbook after its file changed.
+#[derive(Serialize, Clone, Debug)]
+pub struct HandbookReload {
+    pub path: String,
+    /// Number of entries of the new handbook, `None` when it failed to load.
+    pub entries: Option<usize>,
+    /// Problems found in a txt handbook that was still loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<ParseReport>,
+    /// Why the new handbook failed to load, in which case the previous one
+    /// is still used.
+    pub error: Option<String>,
+}
+
+/// Calls a function whenever a file changes, until dropped.
+pub struct FileWatcher {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// Watches the directory of `path` rather than the file itself, so the
+    /// file is still followed after being replaced through a rename, as
+    /// editors and the handbook cache do.
+    pub fn new(path: &Path, on_change: impl Fn() + Send + 'static) -> Result<Self, String> {
+        Self::with_settle(path, SETTLE, on_change)
+    }
+
+    /// Same as [`FileWatcher::new`], waiting for the file to stay unchanged
+    /// for `settle` instead of [`SETTLE`].
+    pub fn with_settle(
+        path: &Path,
+        settle: Duration,
+        on_change: impl Fn() + Send + 'static,
+    ) -> Result<Self, String> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| format!("Not a file: {}", path.display()))?;
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let directory = fs::canonicalize(directory).map_err(|e| e.to_string())?;
+        let path = directory.join(file_name);
+
+        let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher = notify::recommended_watcher(sender).map_err(|e| e.to_string())?;
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(|e| e.to_string())?;
+
+        let watched = path.clone();
+        let concerns_file = move |event: notify::Result<Event>| match event {
+            Ok(event) => {
+                !matches!(event.kind, EventKind::Access(_)) && event.paths.contains(&watched)
+            }
+            Err(e) => {
+                warn!("Error while watching {}: {}", watched.display(), e);
+                false
+            }
+        };
+        // Runs until the watcher, and with it the sender, is dropped.
+        thread::spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                if !concerns_file(event) {
+                    continue;
+                }
+                loop {
+                    match receiver.recv_timeout(settle) {
+                        Ok(_) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                on_change();
+            }
+        });
+
+        Ok(FileWatcher {
+            path,
+            _watcher: watcher,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Reloads the default handbook when its file changes.
+pub struct HandbookWatcher {
+    app_handle: AppHandle,
+    path: String,
+    _file: FileWatcher,
+}
+
+impl HandbookWatcher {
+    fn start(app_handle: AppHandle, path: &str) -> Result<Self, String> {
+        let file = {
+            let app_handle = app_handle.clone();
+            let watched = path.to_string();
+            FileWatcher::new(Path::new(path), move || reload(&app_handle, &watched))?
+        };
+        info!("Watching handbook {}", file.path().display());
+        Ok(HandbookWatcher {
+            app_handle,
+            path: path.to_string(),
+            _file: file,
+        })
+    }
+}
+
+fn reload(app_handle: &AppHandle, path: &str) {
+    // The path may have changed while the change was settling.
+    if *HANDBOOK_PATH.read().unwrap() != path {
+        return;
+    }
+    // Reloads keep the mode the handbook was loaded with.
+    let strict = HANDBOOKS
+        .read()
+        .unwrap()
+        .get(DEFAULT_HANDBOOK)
+        .is_some_and(|handbook| handbook.strict);
+    let reload = match reload_default_handbook(path, strict) {
+        Ok(Some(info)) => {
+            info!("Reloaded handbook {} ({} entries)", path, info.entries);
+            HandbookReload {
+                path: path.to_string(),
+                entries: Some(info.entries),
+                report: info.report,
+                error: None,
+            }
+        }
+        Ok(None) => {
+            info!(
+                "Dropped the reload of {}, another handbook was selected",
+                path
+            );
+            return;
+        }
+        Err(e) => {
+            warn!(
+                "Keeping the previous handbook, failed to reload {}: {}",
+                path, e
+            );
+            HandbookReload {
+                path: path.to_string(),
+                entries: None,
+                report: None,
+                error: Some(e),
+            }
+        }
+    };
+    if let Err(e) = app_handle.emit(RELOAD_EVENT, reload) {
+        error!("Failed to emit {}: {}", RELOAD_EVENT, e);
+    }
+}
+
+/// Starts or stops reloading the handbook set with `update_path_handbook`
+/// whenever its file changes. Every reload emits [`RELOAD_EVENT`].
+#[tauri::command]
+pub fn watch_handbook(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut watcher = HANDBOOK_WATCHER.lock().map_err(|e| e.to_string())?;
+    *watcher = None;
+    if enabled {
+        let path = HANDBOOK_PATH.read().map_err(|e| e.to_string())?.clone();
+        if path.is_empty() {
+            return Err(GmError::PathEmpty.to_string());
+        }
+        *watcher = Some(HandbookWatcher::start(app_handle, &path)?);
+    }
+    Ok(())
+}
+
+/// Moves an active watcher to the current handbook path. The path is read
+/// under the watcher lock, so the last of several path updates wins.
+pub(crate) fn retarget() {
+    let mut watcher = HANDBOOK_WATCHER.lock().unwrap();
+    let path = HANDBOOK_PATH.read().unwrap().clone();
+    let Some(current) = watcher.take_if(|current| current.path != path) else {
+        return;
+    };
+    match HandbookWatcher::start(current.app_handle.clone(), &path) {
+        Ok(restarted) => *watcher = Some(restarted),
+        Err(e) => error!(
+            "Stopped watching the handbook, failed to watch {}: {}",
+            path, e
+        ),
+    }
+}
@@ -1,3 +1,4 @@
+use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -5,7 +6,7 @@ use std::fmt::Formatter;
 use std::str::FromStr;
 pub type Gmhandbook = Vec<GmhandbookElement>;
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Encode, Decode)]
 pub struct GmhandbookElement {
     pub id: i64,
     pub name: NameUnion,
@@ -25,7 +26,7 @@ pub struct GmhandbookElement {
     pub gmhandbook_type: Option<Type>,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, Encode, Decode)]
 pub enum Category {
     Achievements,
     Artifacts,
@@ -48,12 +49,38 @@ pub enum Category {
     BattleMonsters,
     Mazes,
     Gadgets,
+    #[serde(rename = "Light Cones")]
+    LightCones,
+    Relics,
     Unknown,
 }
 
-impl fmt::Display for Category {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let category_str = match self {
+impl Category {
+    pub const ALL: [Category; 20] = [
+        Category::Achievements,
+        Category::Artifacts,
+        Category::Characters,
+        Category::Dungeons,
+        Category::Materials,
+        Category::Monsters,
+        Category::Quests,
+        Category::Scenes,
+        Category::Weapons,
+        Category::Avatars,
+        Category::Items,
+        Category::PropsLC,
+        Category::NPCMonstersLC,
+        Category::BattleStages,
+        Category::BattleMonsters,
+        Category::Mazes,
+        Category::Gadgets,
+        Category::LightCones,
+        Category::Relics,
+        Category::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
             Category::Achievements => "Achievements",
             Category::Artifacts => "Artifacts",
             Category::Characters => "Characters",
@@ -71,9 +98,22 @@ impl fmt::Display for Category {
             Category::BattleMonsters => "Battle Monsters",
             Category::Mazes => "Mazes",
             Category::Gadgets => "Gadgets",
+            Category::LightCones => "Light Cones",
+            Category::Relics => "Relics",
             Category::Unknown => "Unknown",
-        };
-        write!(f, "{}", category_str)
+        }
+    }
+
+    /// Whether both categories are listed under the same name, so selecting
+    /// "Characters" also selects Star Rail avatars.
+    pub fn same_listing(&self, other: &Category) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
     }
 }
 
@@ -99,16 +139,26 @@ impl FromStr for Category {
             "Battle Monsters" => Ok(Category::BattleMonsters),
             "Mazes" => Ok(Category::Mazes),
             "Gadgets" => Ok(Category::Gadgets),
+            "Light Cones" => Ok(Category::LightCones),
+            "Relics" => Ok(Category::Relics),
             _ => Err(()),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct Commands {
-    // pub gc: GcCommand,
-    pub gc: HashMap<String, Command>,
-    pub gio: HashMap<String, Command>,
+/// Commands of an entry, in the shape `generate_handbook` writes them for
+/// each game.
+#[derive(Serialize, Deserialize, Clone, Encode, Decode)]
+#[serde(untagged)]
+pub enum Commands {
+    /// Separate commands for GC and GIO servers.
+    GenshinImpact {
+        // gc: GcCommand,
+        gc: HashMap<String, Command>,
+        gio: HashMap<String, Command>,
+    },
+    /// A single set of commands, keyed `command_1`, `command_2`, ...
+    StarRail(HashMap<String, Command>),
 }
 
 // #[derive(Serialize, Deserialize, Clone)]
@@ -133,7 +183,7 @@ pub struct Commands {
 //     pub command_3: Option<Command>,
 // }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Encode, Decode)]
 pub struct Command {
     pub name: String,
     pub command: String,
@@ -195,7 +245,7 @@ impl fmt::Display for Description {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Encode, Decode)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Type {
     #[serde(rename = "SCENE_DUNGEON")]
@@ -210,7 +260,23 @@ pub enum Type {
     World,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl FromStr for Type {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_uppercase();
+        match s.strip_prefix("SCENE_").unwrap_or(&s) {
+            "DUNGEON" => Ok(Type::Dungeon),
+            "HOME_ROOM" | "HOMEROOM" => Ok(Type::HomeRoom),
+            "HOME_WORLD" | "HOMEWORLD" => Ok(Type::HomeWorld),
+            "ROOM" => Ok(Type::Room),
+            "WORLD" => Ok(Type::World),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Encode, Decode)]
 #[serde(untagged)]
 pub enum NameUnion {
     Description(HashMap<String, String>),
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Category {
     Characters,
     Materials,
@@ -15,4 +15,5 @@ pub enum Category {
     #[serde(rename = "Light Cones")]
     LightCones,
     Relics,
+    Avatars,
 }
@@ -5,7 +5,7 @@ pub mod commands;
 pub mod gi;
 pub mod sr;
 
-#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Language {
     EN,
@@ -25,6 +25,47 @@ pub enum Language {
     VI,
 }
 
+impl Language {
+    pub const ALL: [Language; 15] = [
+        Language::EN,
+        Language::FR,
+        Language::ID,
+        Language::JP,
+        Language::RU,
+        Language::TH,
+        Language::Chs,
+        Language::Cht,
+        Language::DE,
+        Language::ES,
+        Language::IT,
+        Language::KR,
+        Language::PT,
+        Language::TR,
+        Language::VI,
+    ];
+
+    /// Key used for this language in handbook name and description maps.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Language::EN => "EN",
+            Language::FR => "FR",
+            Language::ID => "ID",
+            Language::JP => "JP",
+            Language::RU => "RU",
+            Language::TH => "TH",
+            Language::Chs => "CHS",
+            Language::Cht => "CHT",
+            Language::DE => "DE",
+            Language::ES => "ES",
+            Language::IT => "IT",
+            Language::KR => "KR",
+            Language::PT => "PT",
+            Language::TR => "TR",
+            Language::VI => "VI",
+        }
+    }
+}
+
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1,6 +1,5 @@
 pub mod characters;
 pub mod items;
 pub mod light_cones;
-pub mod mission;
 pub mod monster;
 pub mod relics;
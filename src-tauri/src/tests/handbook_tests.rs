@@ -499,3 +499,301 @@ fn test_generate_command() {
         CommandMap::HonkaiStarRail(map) => assert_eq!(map.len(), 0),
     }
 }
+
+#[test]
+fn test_generated_handbook_round_trip() {
+    use crate::generate::artifacts::ArtifactResult;
+    use crate::generate::characters::CharacterResult;
+    use crate::generate::commands::generate_command;
+    use crate::generate::materials::MaterialsResult;
+    use crate::generate::monsters::MonstersResult;
+    use crate::generate::scenes::ScenesResult;
+    use crate::generate::weapons::WeaponResult;
+    use crate::generate::ResultData;
+    use crate::search::gi::parse_json_file;
+    use crate::structure::gm::{self, Category as GmCategory};
+    use crate::structure::handbook::gi::scenes::SceneType;
+    use crate::structure::handbook::{category::Category, Language};
+
+    let names = |name: &str| {
+        BTreeMap::from([
+            (Language::EN, name.to_string()),
+            (Language::Chs, format!("{} (CHS)", name)),
+        ])
+    };
+    let genshin = |category, id| generate_command(category, id, "/give", GameType::GenshinImpact);
+    let star_rail = |category, id| generate_command(category, id, "", GameType::HonkaiStarRail);
+
+    let generated = vec![
+        ResultData::Characters(CharacterResult {
+            id: 10000007,
+            name: names("Traveler"),
+            description: None,
+            image: "traveler.png".to_string(),
+            category: Category::Characters,
+            rarity: Some(5),
+            commands: genshin(Category::Characters, 10000007),
+        }),
+        ResultData::Characters(CharacterResult {
+            id: 1001,
+            name: names("March 7th"),
+            description: None,
+            image: "1001.png".to_string(),
+            category: Category::Avatars,
+            rarity: Some(4),
+            commands: star_rail(Category::Avatars, 1001),
+        }),
+        ResultData::Weapons(WeaponResult {
+            id: 23002,
+            name: names("Something Irreplaceable"),
+            description: names(""),
+            icon: "23002.png".to_string(),
+            rarity: 5,
+            category: Category::LightCones,
+            commands: star_rail(Category::LightCones, 23002),
+        }),
+        ResultData::Artifacts(ArtifactResult {
+            id: 31011,
+            name: names("Passerby's Rejuvenated Wooden Hairstick"),
+            description: names(""),
+            image: "31011.png".to_string(),
+            category: Category::Relics,
+            rarity: 2,
+            commands: star_rail(Category::Relics, 31011),
+        }),
+        ResultData::Materials(MaterialsResult {
+            id: 1,
+            name: names("Stellar Jade"),
+            description: names("Currency"),
+            image: "1.png".to_string(),
+            category: Category::Items,
+            rarity: None,
+            commands: star_rail(Category::Items, 1),
+        }),
+        ResultData::Monsters(MonstersResult {
+            id: 8003010,
+            name: names("Cocolia"),
+            description: None,
+            image: None,
+            category: Category::Monsters,
+            commands: Some(star_rail(Category::Monsters, 8003010)),
+        }),
+        ResultData::Scenes(ScenesResult {
+            id: 3,
+            scene_type: SceneType::World,
+            name: "Teyvat".to_string(),
+            category: Category::Scenes,
+            commands: genshin(Category::Scenes, 3),
+        }),
+    ];
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("handbook.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&generated).unwrap()).unwrap();
+    let handbook = parse_json_file(&path).unwrap();
+
+    let categories: Vec<(i64, GmCategory)> = handbook
+        .iter()
+        .map(|item| (item.id, item.category.clone()))
+        .collect();
+    assert_eq!(
+        categories,
+        vec![
+            (10000007, GmCategory::Characters),
+            (1001, GmCategory::Avatars),
+            (23002, GmCategory::LightCones),
+            (31011, GmCategory::Relics),
+            (1, GmCategory::Items),
+            (8003010, GmCategory::Monsters),
+            (3, GmCategory::Scenes),
+        ]
+    );
+
+    match &handbook[0].commands {
+        Some(gm::Commands::GenshinImpact { gc, gio }) => {
+            assert_eq!(gc["command_1"].command, "/give 10000007");
+            assert_eq!(gio["command_1"].command, "avatar add 10000007");
+        }
+        _ => panic!("expected GC and GIO commands"),
+    }
+    match &handbook[2].commands {
+        Some(gm::Commands::StarRail(commands)) => {
+            assert_eq!(commands.len(), 5);
+            assert_eq!(commands["command_1"].command, "/give 23002");
+        }
+        _ => panic!("expected Star Rail commands"),
+    }
+    match &handbook[4].commands {
+        Some(gm::Commands::StarRail(commands)) => {
+            assert_eq!(commands["command_2"].command, "/give 1 x<amount>");
+        }
+        _ => panic!("expected Star Rail commands"),
+    }
+    match &handbook[1].name {
+        gm::NameUnion::Description(names) => assert_eq!(names["CHS"], "March 7th (CHS)"),
+        gm::NameUnion::String(_) => panic!("expected localized names"),
+    }
+    assert!(matches!(handbook[6].name, gm::NameUnion::String(_)));
+    assert_eq!(handbook[6].gmhandbook_type, Some(gm::Type::World));
+
+    // Loaded handbooks serialize back to the same shape.
+    let reserialized: serde_json::Value = serde_json::to_value(&handbook).unwrap();
+    let original: serde_json::Value = serde_json::to_value(&generated).unwrap();
+    assert_eq!(reserialized[2]["commands"], original[2]["commands"]);
+    assert_eq!(reserialized[0]["commands"], original[0]["commands"]);
+    assert_eq!(reserialized[2]["category"], "Light Cones");
+}
+
+#[test]
+fn test_select_for_game() {
+    use crate::generate::{select_for_game, GameTypeFandom, SelectHandbookArgs};
+
+    let selections = |names: &[&str]| Some(names.iter().map(|name| name.to_string()).collect());
+
+    assert_eq!(
+        select_for_game(&None, &GameTypeFandom::StarRail).unwrap(),
+        vec![
+            SelectHandbookArgs::Monsters,
+            SelectHandbookArgs::Avatars,
+            SelectHandbookArgs::LightCones,
+            SelectHandbookArgs::Relics,
+            SelectHandbookArgs::Items,
+        ]
+    );
+    assert!(!select_for_game(&None, &GameTypeFandom::GenshinImpact)
+        .unwrap()
+        .contains(&SelectHandbookArgs::LightCones));
+    assert_eq!(
+        select_for_game(
+            &selections(&["Light Cones", "relics"]),
+            &GameTypeFandom::StarRail
+        )
+        .unwrap(),
+        vec![SelectHandbookArgs::LightCones, SelectHandbookArgs::Relics]
+    );
+
+    // Selections of the other game are rejected
+    let error = select_for_game(
+        &selections(&["Weapons", "Monsters", "Artifacts"]),
+        &GameTypeFandom::StarRail,
+    )
+    .unwrap_err();
+    assert!(error.contains("weapons, artifacts"), "{}", error);
+    assert!(error.contains("light-cones"), "{}", error);
+    assert!(select_for_game(&selections(&["items"]), &GameTypeFandom::GenshinImpact).is_err());
+    // LunarCore has no mission command, so missions are not generated
+    assert!(select_for_game(&selections(&["missions"]), &GameTypeFandom::StarRail).is_err());
+}
+
+#[test]
+fn test_result_accumulator() {
+    use crate::generate::commands::generate_command;
+    use crate::generate::dungeons::DungeonsResult;
+    use crate::generate::{ResultAccumulator, ResultData};
+    use crate::structure::handbook::{category::Category, Language};
+
+    let dungeon = |id: i64, language: Language, name: &str| DungeonsResult {
+        id,
+        name: BTreeMap::from([(language, name.to_string())]),
+        description: BTreeMap::new(),
+        category: Category::Dungeons,
+        commands: generate_command(
+            Category::Dungeons,
+            id as u32,
+            "/tp",
+            GameType::GenshinImpact,
+        ),
+    };
+
+    let mut entries: ResultAccumulator<DungeonsResult> = ResultAccumulator::default();
+    for (language, names) in [
+        (Language::EN, ["Domain of Guyun", "Midsummer Courtyard"]),
+        (Language::JP, ["孤雲閣", "華清庭"]),
+    ] {
+        for (id, name) in [5, 7].into_iter().zip(names) {
+            match entries.get_mut(id) {
+                Some(existing) => {
+                    existing.name.insert(language.clone(), name.to_string());
+                }
+                None => entries.insert(id, dungeon(id, language.clone(), name)),
+            }
+        }
+    }
+
+    // Languages are merged into the entry first generated, in that order
+    let merged: Vec<(i64, usize)> = entries
+        .into_results(ResultData::Dungeons)
+        .map(|result| match result {
+            ResultData::Dungeons(dungeon) => (dungeon.id, dungeon.name.len()),
+            _ => panic!("expected dungeons"),
+        })
+        .collect();
+    assert_eq!(merged, vec![(5, 2), (7, 2)]);
+}
+
+#[test]
+fn test_sorted_results() {
+    use crate::generate::commands::generate_command;
+    use crate::generate::dungeons::DungeonsResult;
+    use crate::generate::quests::MainQuestResult;
+    use crate::generate::{sort_results, ResultData};
+    use crate::structure::handbook::{category::Category, Language};
+
+    let names = || {
+        BTreeMap::from([
+            (Language::Chs, "CHS".to_string()),
+            (Language::JP, "JP".to_string()),
+            (Language::EN, "EN".to_string()),
+        ])
+    };
+    let quest = |id: i64| {
+        ResultData::Quests(MainQuestResult {
+            id,
+            name: names(),
+            description: BTreeMap::new(),
+            category: Category::Quests,
+            commands: generate_command(Category::Quests, id as u32, "/q", GameType::GenshinImpact),
+        })
+    };
+    let dungeon = |id: i64| {
+        ResultData::Dungeons(DungeonsResult {
+            id,
+            name: names(),
+            description: BTreeMap::new(),
+            category: Category::Dungeons,
+            commands: generate_command(
+                Category::Dungeons,
+                id as u32,
+                "/tp",
+                GameType::GenshinImpact,
+            ),
+        })
+    };
+
+    let mut result = vec![quest(352), dungeon(7), quest(301), dungeon(5)];
+    sort_results(&mut result);
+    let json = serde_json::to_string(&result).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let order: Vec<(&str, i64)> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["category"].as_str().unwrap(),
+                entry["id"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            ("Quests", 301),
+            ("Quests", 352),
+            ("Dungeons", 5),
+            ("Dungeons", 7)
+        ]
+    );
+    // Languages are written in a fixed order
+    assert!(json.starts_with(r#"[{"id":301,"name":{"EN":"EN","JP":"JP","CHS":"CHS"}"#));
+}
@@ -1,2 +1,3 @@
 pub mod handbook_tests;
+pub mod search_tests;
 pub mod utility_tests;
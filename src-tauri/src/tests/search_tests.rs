@@ -0,0 +1,1560 @@
+use pretty_assertions::assert_eq;
+
+use crate::structure::gm::{Category, Gmhandbook, GmhandbookElement, NameUnion, Type};
+
+#[test]
+fn test_fuzzy_score() {
+    use crate::search::fuzzy::{score, SCORE_EXACT, SCORE_EXACT_TOKENS};
+
+    // Exact and punctuation-insensitive matches
+    assert_eq!(score("amos' bow", "amos' bow"), Some(SCORE_EXACT));
+    assert_eq!(score("amos bow", "amos' bow"), Some(SCORE_EXACT_TOKENS - 1));
+
+    // Prefix beats substring, substring beats typo
+    let prefix = score("sky", "skyward blade").unwrap();
+    let substring = score("ward", "skyward blade").unwrap();
+    let typo = score("skywrad", "skyward blade").unwrap();
+    assert!(prefix > substring);
+    assert!(substring > typo);
+
+    // Every query word has to match something
+    assert_eq!(score("amos sword", "amos' bow"), None);
+    assert_eq!(score("xyz", "amos' bow"), None);
+
+    // Short words are not typo tolerant
+    assert_eq!(score("bwo", "amos' bow"), None);
+
+    // Shorter names rank first within a tier
+    assert!(score("dull", "dull blade").unwrap() > score("dull", "dull blade (awakened)").unwrap());
+}
+
+#[test]
+fn test_edit_distance() {
+    use crate::search::fuzzy::edit_distance;
+
+    let chars = |s: &str| s.chars().collect::<Vec<_>>();
+    assert_eq!(
+        edit_distance(&chars("kitten"), &chars("sitting"), 3),
+        Some(3)
+    );
+    assert_eq!(edit_distance(&chars("kitten"), &chars("sitting"), 2), None);
+    assert_eq!(edit_distance(&chars("ab"), &chars("ba"), 1), Some(1));
+    assert_eq!(edit_distance(&chars("same"), &chars("same"), 0), Some(0));
+}
+
+fn element(id: i64, category: Category, names: &[(&str, &str)]) -> GmhandbookElement {
+    GmhandbookElement {
+        id,
+        name: NameUnion::Description(
+            names
+                .iter()
+                .map(|(language, name)| (language.to_string(), name.to_string()))
+                .collect(),
+        ),
+        description: None,
+        image: None,
+        category,
+        rarity: None,
+        commands: None,
+        icon: None,
+        gmhandbook_type: None,
+    }
+}
+
+fn search_handbook(
+    handbook: &Gmhandbook,
+    index: &crate::search::index::HandbookIndex,
+    request: &crate::search::gi::SearchRequest,
+) -> Result<crate::search::gi::SearchResponse, crate::search::gi::GmError> {
+    use crate::search::gi::{search_handbooks, SearchSource};
+
+    let source = SearchSource {
+        handbook,
+        index,
+        name: None,
+        game: None,
+    };
+    search_handbooks(&[source], request)
+}
+
+type SampleEntry<'a> = (i64, Category, &'a [(&'a str, &'a str)], Option<i64>);
+
+fn sample_handbook() -> Gmhandbook {
+    let entries: [SampleEntry; 10] = [
+        (
+            15502,
+            Category::Weapons,
+            &[("EN", "Amos' Bow"), ("JP", "アモスの弓")],
+            Some(5),
+        ),
+        (
+            11501,
+            Category::Weapons,
+            &[
+                ("EN", "Aquila Favonia"),
+                ("JP", "風鷹剣"),
+                ("CHS", "风鹰剑"),
+            ],
+            Some(5),
+        ),
+        (
+            11502,
+            Category::Weapons,
+            &[
+                ("EN", "Skyward Blade"),
+                ("JP", "天空の刃"),
+                ("RU", "Небесный меч"),
+            ],
+            Some(5),
+        ),
+        (
+            12502,
+            Category::Weapons,
+            &[("EN", "Wolf's Gravestone"), ("JP", "狼の末路")],
+            Some(5),
+        ),
+        (
+            15501,
+            Category::Weapons,
+            &[("EN", "Skyward Harp"), ("JP", "天空の翼")],
+            Some(5),
+        ),
+        (
+            13401,
+            Category::Weapons,
+            &[("EN", "Dragon's Bane"), ("JP", "匣中滅龍")],
+            Some(4),
+        ),
+        (
+            101,
+            Category::Materials,
+            &[("EN", "Crystal Chunk"), ("JP", "水晶塊")],
+            Some(1),
+        ),
+        (
+            20011201,
+            Category::Monsters,
+            &[("EN", "Dendro Slime"), ("JP", "草スライム")],
+            None,
+        ),
+        (
+            20011202,
+            Category::Monsters,
+            &[("EN", "Large Dendro Slime")],
+            None,
+        ),
+        (3, Category::Scenes, &[("EN", "Teyvat")], None),
+    ];
+    let mut handbook: Gmhandbook = entries
+        .into_iter()
+        .map(|(id, category, names, rarity)| GmhandbookElement {
+            rarity,
+            ..element(id, category, names)
+        })
+        .collect();
+    handbook[9].gmhandbook_type = Some(Type::World);
+    handbook
+}
+
+#[test]
+fn test_handbook_index_search() {
+    use crate::search::filter::NumberRange;
+    use crate::search::index::HandbookIndex;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let ids = |query: &str, language: &str| {
+        let mut result = index.search(query, &[language]);
+        result.sort_by_key(|&(entry, score, _)| (std::cmp::Reverse(score), entry));
+        result
+            .into_iter()
+            .map(|(entry, _, _)| handbook[entry].id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids("amos bow", "EN"), vec![15502]);
+    assert_eq!(ids("SKYWARD", "EN"), vec![15501, 11502]);
+    assert_eq!(ids("skyward harp", "EN"), vec![15501]);
+    assert_eq!(ids("ward", "EN"), vec![15501, 11502]);
+    assert_eq!(ids("gravestnoe", "EN"), vec![12502]);
+    assert_eq!(ids("aquila", "JP"), Vec::<i64>::new());
+    assert_eq!(ids("天空の刃", "JP")[0], 11502);
+    let exact = |id| {
+        [NumberRange {
+            min: Some(id),
+            max: Some(id),
+        }]
+    };
+    assert_eq!(index.by_id_ranges(&exact(11501)), vec![1]);
+    assert!(index.by_id_ranges(&exact(1)).is_empty());
+}
+
+#[test]
+fn test_search_filters() {
+    use crate::search::filter::{NumberRange, SearchFilter};
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let ids = |search: &str, filter: SearchFilter, limit: Option<usize>| {
+        let request = SearchRequest {
+            search,
+            language: "EN",
+            filter,
+            limit,
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|result| result.item.id)
+            .collect::<Vec<_>>()
+    };
+
+    let five_star_weapons = SearchFilter {
+        categories: Some(vec![Category::Weapons]),
+        rarity: Some(NumberRange {
+            min: Some(5),
+            max: None,
+        }),
+        types: None,
+    };
+    assert_eq!(
+        ids("", five_star_weapons.clone(), None),
+        vec![15502, 11501, 11502, 12502, 15501]
+    );
+    // The limit is applied after filtering
+    assert_eq!(ids("", five_star_weapons, Some(2)), vec![15502, 11501]);
+
+    let four_star = SearchFilter {
+        rarity: Some(NumberRange {
+            min: Some(4),
+            max: Some(4),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(ids("dragon", four_star.clone(), None), vec![13401]);
+    assert_eq!(ids("slime", four_star, None), Vec::<i64>::new());
+
+    let monsters = SearchFilter {
+        categories: Some(vec![Category::Monsters]),
+        ..Default::default()
+    };
+    assert_eq!(ids("slime", monsters, Some(1)), vec![20011201]);
+
+    let worlds = SearchFilter {
+        types: Some(vec![Type::World]),
+        ..Default::default()
+    };
+    assert_eq!(ids("", worlds, None), vec![3]);
+
+    let nothing = SearchFilter {
+        categories: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert_eq!(ids("slime", nothing, None), Vec::<i64>::new());
+}
+
+#[test]
+fn test_query_parse() {
+    use crate::search::filter::NumberRange;
+    use crate::search::query::{Predicate, Query, Term};
+
+    let term = |negated, predicate| Term { negated, predicate };
+    let query = Query::parse(r#"cat:Weapons rarity:>=4 id:11400..11599 "wolf" -bow"#).unwrap();
+    assert_eq!(
+        query.groups,
+        vec![vec![
+            term(false, Predicate::Category(vec![Category::Weapons])),
+            term(
+                false,
+                Predicate::Rarity(NumberRange {
+                    min: Some(4),
+                    max: None
+                })
+            ),
+            term(
+                false,
+                Predicate::Id(vec![NumberRange {
+                    min: Some(11400),
+                    max: Some(11599)
+                }])
+            ),
+            term(false, Predicate::Phrase("wolf".to_string())),
+            term(true, Predicate::Text("bow".to_string())),
+        ]]
+    );
+
+    let query = Query::parse(r#"slime OR cat:"battle stages",Scenes | type:world"#).unwrap();
+    assert_eq!(
+        query.groups,
+        vec![
+            vec![term(false, Predicate::Text("slime".to_string()))],
+            vec![term(
+                false,
+                Predicate::Category(vec![Category::BattleStages, Category::Scenes])
+            )],
+            vec![term(false, Predicate::Type(vec![Type::World]))],
+        ]
+    );
+
+    // Unknown fields are plain text, the empty query matches everything
+    assert_eq!(
+        Query::parse("quest:abc").unwrap().groups,
+        vec![vec![term(false, Predicate::Text("quest:abc".to_string()))]]
+    );
+    assert_eq!(Query::parse("").unwrap().groups, vec![Vec::new()]);
+
+    assert!(Query::parse("cat:Weaponz").is_err());
+    assert!(Query::parse("rarity:>=five").is_err());
+    // Exclusive bounds past the ends of i64 are errors, not overflows
+    assert!(Query::parse("rarity:>9223372036854775807").is_err());
+    assert!(Query::parse("id:<-9223372036854775808").is_err());
+}
+
+#[test]
+fn test_find_blank_search() {
+    use crate::search::gi::{find, GmError};
+
+    // A search of spaces is empty, rather than listing every entry
+    for search in ["", "   ", "\t "] {
+        let error = find(search, "EN", None, None, None, None, None, None)
+            .err()
+            .unwrap();
+        assert_eq!(error, GmError::EmptySearchTerm.to_string());
+    }
+}
+
+#[test]
+fn test_search_query() {
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let ids = |search: &str| {
+        let request = SearchRequest {
+            search,
+            language: "EN",
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|result| result.item.id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        ids("cat:Weapons rarity:>=4 id:11400..11599"),
+        vec![11501, 11502]
+    );
+    assert_eq!(ids(r#"cat:Weapons "wolf""#), vec![12502]);
+    assert_eq!(ids("skyward -harp"), vec![11502]);
+    assert_eq!(ids("slime -cat:Monsters"), Vec::<i64>::new());
+    assert_eq!(ids("rarity:<5 -cat:Weapons"), vec![101]);
+    assert_eq!(ids("amos OR dragon"), vec![15502, 13401]);
+    assert_eq!(ids("11501"), vec![11501]);
+}
+
+#[test]
+fn test_search_pagination() {
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let page = |offset: usize, limit: Option<usize>| {
+        let request = SearchRequest {
+            search: "cat:Weapons",
+            language: "EN",
+            offset,
+            limit,
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request).unwrap()
+    };
+    let ids = |items: &[crate::search::gi::SearchResult]| {
+        items
+            .iter()
+            .map(|result| result.item.id)
+            .collect::<Vec<_>>()
+    };
+
+    let first = page(0, Some(4));
+    assert_eq!(first.total, 6);
+    assert_eq!(ids(&first.items), vec![15502, 11501, 11502, 12502]);
+    assert_eq!(first.next_offset, Some(4));
+    assert_eq!(first.query, "cat:Weapons");
+
+    let second = page(4, Some(4));
+    assert_eq!(second.total, 6);
+    assert_eq!(ids(&second.items), vec![15501, 13401]);
+    assert_eq!(second.next_offset, None);
+
+    let past_end = page(10, Some(4));
+    assert_eq!(past_end.total, 6);
+    assert!(past_end.items.is_empty());
+    assert_eq!(past_end.next_offset, None);
+
+    let all = page(0, None);
+    assert_eq!(all.items.len(), 6);
+    assert_eq!(all.next_offset, None);
+}
+
+#[test]
+fn test_search_languages() {
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+    use crate::search::language::{detect, LanguageMode};
+    use crate::structure::handbook::Language;
+    use std::str::FromStr;
+
+    assert_eq!(detect("天空の刃"), vec![Language::JP]);
+    assert_eq!(detect("небесный"), vec![Language::RU]);
+    assert_eq!(
+        detect("風鷹剣"),
+        vec![Language::Chs, Language::Cht, Language::JP]
+    );
+    assert!(detect("amos").contains(&Language::EN));
+    assert!(detect("11501").is_empty());
+    assert_eq!(LanguageMode::from_str("ALL"), Ok(LanguageMode::All));
+    assert_eq!(
+        LanguageMode::from_str("pt"),
+        Ok(LanguageMode::Single(Language::PT))
+    );
+    assert!(LanguageMode::from_str("xx").is_err());
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let search = |search: &str, language: &str| {
+        let request = SearchRequest {
+            search,
+            language,
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request)
+    };
+    let hits = |query: &str, language: &str| {
+        search(query, language)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|result| (result.item.id, result.matched_language))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(hits("небесный", "auto"), vec![(11502, Some("RU"))]);
+    assert_eq!(search("небесный", "auto").unwrap().languages, vec!["RU"]);
+    assert_eq!(
+        hits("天空", "all"),
+        vec![(11502, Some("JP")), (15501, Some("JP"))]
+    );
+    assert_eq!(hits("天空", "EN"), vec![]);
+    assert_eq!(hits("skyward blade", "all"), vec![(11502, Some("EN"))]);
+    assert_eq!(hits("teyvat", "auto"), vec![(3, Some("EN"))]);
+    assert_eq!(hits("11501", "auto"), vec![(11501, None)]);
+    assert!(search("amos", "xx").is_err());
+}
+
+#[test]
+fn test_search_normalization() {
+    use crate::search::index::HandbookIndex;
+    use crate::search::normalize::{normalize, transliterate};
+
+    assert_eq!(normalize("Ｆａｖｏｎｉｕｓ"), "favonius");
+    assert_eq!(normalize("Favónius Đàn"), "favonius dan");
+    assert_eq!(normalize("Straße"), "strasse");
+    assert_eq!(normalize("ｱﾓｽ"), "アモス");
+    assert_eq!(normalize("ガイド"), "ガイド");
+    assert_eq!(normalize("Йогурт"), "йогурт");
+    // Marks are only removed from Latin letters, Vietnamese included
+    assert_eq!(normalize("Favo\u{301}nius"), "favonius");
+    assert_eq!(normalize("Tiếng Việt"), "tieng viet");
+    assert_eq!(normalize("Йогурт"), normalize("И\u{306}огурт"));
+    assert_ne!(normalize("Йогурт"), normalize("Иогурт"));
+    assert_eq!(normalize("กิน"), "กิน");
+    assert_ne!(normalize("กิน"), normalize("กน"));
+    assert_eq!(normalize("किताब"), "किताब");
+    assert_ne!(normalize("किताब"), normalize("कताब"));
+    assert_eq!(transliterate("amos"), None);
+    assert_eq!(transliterate("風鷹剣"), transliterate("风鹰剑"));
+    assert_eq!(transliterate("アモス").as_deref(), Some("amosu"));
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let ids = |query: &str, language: &str| {
+        let mut result = index.search(query, &[language]);
+        result.sort_by_key(|&(entry, score, _)| (std::cmp::Reverse(score), entry));
+        result
+            .into_iter()
+            .map(|(entry, _, _)| handbook[entry].id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids("Áquilá", "EN"), vec![11501]);
+    assert_eq!(ids("ＡＱＵＩＬＡ", "EN"), vec![11501]);
+    assert_eq!(ids("風鷹剣", "CHS"), vec![11501]);
+    assert_eq!(ids("feng ying", "CHS"), vec![11501]);
+    assert_eq!(ids("amosu", "JP"), vec![15502]);
+    assert_eq!(ids("ｱﾓｽ", "JP"), vec![15502]);
+}
+
+#[test]
+fn test_search_descriptions() {
+    use crate::search::gi::{MatchField, SearchRequest};
+    use crate::search::index::HandbookIndex;
+    use crate::search::snippet::{Highlight, Snippet};
+
+    let snippet = Snippet::new("Soul of the Knights of Favonius.", &["knights"], &[]);
+    assert_eq!(snippet.text, "Soul of the Knights of Favonius.");
+    assert_eq!(snippet.highlights, vec![Highlight { start: 12, end: 19 }]);
+    let snippet = Snippet::new(&format!("{}Favónius", "a ".repeat(50)), &[], &["favonius"]);
+    assert_eq!(snippet.text, format!("…{}Favónius", "a ".repeat(20)));
+    assert_eq!(snippet.highlights, vec![Highlight { start: 41, end: 49 }]);
+    // Offsets are in UTF-16 code units, as the frontend slices the text
+    let snippet = Snippet::new("🗡️ Knights 𠀀 of Favonius", &["favonius"], &[]);
+    assert_eq!(snippet.highlights, vec![Highlight { start: 18, end: 26 }]);
+    let highlighted: Vec<u16> = snippet.text.encode_utf16().collect();
+    assert_eq!(
+        String::from_utf16(&highlighted[18..26]).unwrap(),
+        "Favonius"
+    );
+
+    let mut handbook = sample_handbook();
+    let describe = |text: &str| Some([("EN".to_string(), text.to_string())].into());
+    handbook[0].description = describe("A bow that once shot arrows toward the skyward heavens.");
+    handbook[1].description = describe("The soul of the Knights of Favonius.");
+    let index = HandbookIndex::build(&handbook);
+    let search = |search: &str, descriptions: bool| {
+        let request = SearchRequest {
+            search,
+            language: "EN",
+            descriptions,
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request).unwrap().items
+    };
+    let ids = |query: &str, descriptions: bool| {
+        search(query, descriptions)
+            .into_iter()
+            .map(|result| (result.item.id, result.matched_field))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids("knights", false), vec![]);
+    assert_eq!(
+        ids("knights", true),
+        vec![(11501, Some(MatchField::Description))]
+    );
+    assert_eq!(
+        ids("skyward", true),
+        vec![
+            (15501, Some(MatchField::Name)),
+            (11502, Some(MatchField::Name)),
+            (15502, Some(MatchField::Description)),
+        ]
+    );
+    assert!(search("skyward", true)[2].score <= 300);
+
+    let result = &search("\"knights of favonius\"", true)[0];
+    assert_eq!(result.matched_language, Some("EN"));
+    let snippet = result.snippet.as_ref().unwrap();
+    assert_eq!(snippet.text, "The soul of the Knights of Favonius.");
+    assert_eq!(snippet.highlights, vec![Highlight { start: 16, end: 35 }]);
+    assert!(search("skyward", true)[0].snippet.is_none());
+}
+
+#[test]
+fn test_search_ids() {
+    use crate::search::filter::NumberRange;
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+    use crate::search::query::parse_ids;
+
+    let range = |min, max| NumberRange {
+        min: Some(min),
+        max: Some(max),
+    };
+    assert_eq!(parse_ids("11501"), Ok(vec![range(11501, 11501)]));
+    assert_eq!(parse_ids("11500-11599"), Ok(vec![range(11500, 11599)]));
+    assert_eq!(
+        parse_ids("1150*").unwrap()[..3],
+        [
+            range(1150, 1150),
+            range(11500, 11509),
+            range(115000, 115099)
+        ]
+    );
+    assert!(parse_ids("11a*").is_err());
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let ids = |search: &str, language: &str| {
+        let request = SearchRequest {
+            search,
+            language,
+            ..Default::default()
+        };
+        search_handbook(&handbook, &index, &request)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|result| result.item.id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids("11501", "JP"), vec![11501]);
+    assert_eq!(ids("11501", "auto"), vec![11501]);
+    assert_eq!(ids("1150*", "JP"), vec![11501, 11502]);
+    assert_eq!(ids("11500-12999", "RU"), vec![11501, 11502, 12502]);
+    assert_eq!(ids("12999-11500", "EN"), Vec::<i64>::new());
+    assert_eq!(ids("id:1550* cat:Weapons", "EN"), vec![15502, 15501]);
+    assert_eq!(ids("2001120*", "EN"), vec![20011201, 20011202]);
+}
+
+#[test]
+fn test_handbook_registry() {
+    use crate::search::gi::{search_handbooks, SearchRequest, SearchSource};
+    use crate::search::index::HandbookIndex;
+    use crate::search::registry::{Game, HandbookRegistry, HandbookTarget, LoadedHandbook};
+
+    let genshin = sample_handbook();
+    let star_rail = vec![
+        element(1001, Category::Avatars, &[("EN", "March 7th")]),
+        element(23002, Category::Items, &[("EN", "Something Irreplaceable")]),
+    ];
+    assert_eq!(Game::detect(&genshin), Game::GenshinImpact);
+    assert_eq!(Game::detect(&star_rail), Game::StarRail);
+
+    let mut registry = HandbookRegistry::default();
+    let loaded = |name: &str, game: Game, path: &str, content: Gmhandbook| {
+        let index = HandbookIndex::build(&content);
+        LoadedHandbook::new(name, game, path, content, index)
+    };
+    registry.insert(loaded("gc", Game::GenshinImpact, "gc.json", genshin));
+    registry.insert(loaded("lc", Game::StarRail, "old.json", Vec::new()));
+    registry.insert(loaded("lc", Game::StarRail, "lc.json", star_rail));
+    let infos: Vec<_> = registry.iter().map(LoadedHandbook::info).collect();
+    assert_eq!(infos.len(), 2);
+    assert_eq!((infos[1].path.as_str(), infos[1].entries), ("lc.json", 2));
+
+    assert_eq!(HandbookTarget::parse(Some("ALL")), HandbookTarget::All);
+    assert_eq!(
+        HandbookTarget::parse(Some("sr")),
+        HandbookTarget::Game(Game::StarRail)
+    );
+    assert_eq!(
+        HandbookTarget::parse(None),
+        HandbookTarget::Named("default".to_string())
+    );
+
+    let search = |registry: &HandbookRegistry, search: &str, target: &str| {
+        let sources: Vec<SearchSource> = registry
+            .select(&HandbookTarget::parse(Some(target)))
+            .into_iter()
+            .map(SearchSource::from)
+            .collect();
+        let request = SearchRequest {
+            search,
+            language: "EN",
+            ..Default::default()
+        };
+        let response = search_handbooks(&sources, &request).unwrap();
+        let items = response
+            .items
+            .into_iter()
+            .map(|result| (result.handbook.unwrap(), result.item.id))
+            .collect::<Vec<_>>();
+        (response.handbooks, items)
+    };
+
+    let (handbooks, items) = search(&registry, "1*", "all");
+    assert_eq!(handbooks, vec!["gc", "lc"]);
+    assert_eq!(items.len(), 8);
+    assert_eq!(items[0], ("gc".to_string(), 15502));
+    assert!(items.contains(&("lc".to_string(), 1001)));
+    assert_eq!(
+        search(&registry, "march", "sr").1,
+        vec![("lc".to_string(), 1001)]
+    );
+    assert_eq!(search(&registry, "march", "gc").1, vec![]);
+    assert!(registry.select(&HandbookTarget::parse(None)).is_empty());
+
+    assert!(registry.remove("gc").is_some());
+    assert!(registry.remove("gc").is_none());
+    assert_eq!(search(&registry, "amos", "all").1, vec![]);
+}
+
+#[test]
+fn test_handbook_cache() {
+    use crate::search::cache::{cache_path, load, HandbookData};
+    use crate::search::gi::parse_json_file;
+    use crate::search::index::HandbookIndex;
+    use crate::search::txt::{parse_txt_file, ParseReport};
+    use std::fs::{self, File};
+    use std::path::Path;
+    use std::time::{Duration, SystemTime};
+
+    type Parsed = Result<(Gmhandbook, Option<ParseReport>), String>;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("gmhandbook.json");
+    fs::write(&path, serde_json::to_string(&sample_handbook()).unwrap()).unwrap();
+    let json = |path: &Path| -> Parsed { Ok((parse_json_file(path)?, None)) };
+    let unparsable =
+        |_: &Path| -> Parsed { panic!("handbook should have been read from the cache") };
+    let ids = |handbook: &Gmhandbook| handbook.iter().map(|item| item.id).collect::<Vec<_>>();
+    let hits = |index: &HandbookIndex| {
+        let mut hits = index.search("skyward", &["EN"]);
+        hits.sort_unstable();
+        hits
+    };
+
+    // The first load parses the source and writes the cache next to it
+    let loaded = load(&path, json).unwrap();
+    assert!(cache_path(&path).exists());
+    assert_eq!(cache_path(&path), dir.path().join("gmhandbook.json.cache"));
+    // without leaving its temporary file behind
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+    // Later loads come from the cache, index included
+    let cached = load(&path, unparsable).unwrap();
+    assert_eq!(ids(&cached.content), ids(&loaded.content));
+    assert_eq!(hits(&cached.index), hits(&loaded.index));
+
+    // A copy with the same contents keeps using the cache
+    let file = File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    drop(file);
+    let cached = load(&path, unparsable).unwrap();
+    assert_eq!(ids(&cached.content), ids(&loaded.content));
+
+    // Changed contents rebuild it
+    let changed = &sample_handbook()[..2];
+    fs::write(&path, serde_json::to_string(changed).unwrap()).unwrap();
+    let rebuilt = load(&path, json).unwrap().content;
+    assert_eq!(ids(&rebuilt), ids(&changed.to_vec()));
+    assert_eq!(
+        ids(&load(&path, unparsable).unwrap().content),
+        ids(&rebuilt)
+    );
+
+    // So does a corrupt cache
+    fs::write(cache_path(&path), b"not a cache").unwrap();
+    assert_eq!(ids(&load(&path, json).unwrap().content), ids(&rebuilt));
+
+    // And a cache whose index refers to entries it does not have
+    let bytes = fs::read(cache_path(&path)).unwrap();
+    type Header = ([u8; 8], u32, String, (u64, u64, u64));
+    let (_, offset): (Header, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+    let mismatched = HandbookData {
+        content: rebuilt[..1].to_vec(),
+        index: HandbookIndex::build(&rebuilt),
+        report: None,
+    };
+    let mut corrupt = bytes[..offset].to_vec();
+    corrupt.extend(bincode::encode_to_vec(&mismatched, bincode::config::standard()).unwrap());
+    fs::write(cache_path(&path), corrupt).unwrap();
+    assert_eq!(ids(&load(&path, json).unwrap().content), ids(&rebuilt));
+
+    // Parse reports of txt handbooks are cached with them
+    let path = dir.path().join("gmhandbook.txt");
+    fs::write(&path, "// Weapons\n11101: Dull Blade\nbroken\n").unwrap();
+    let txt = |path: &Path| -> Parsed {
+        parse_txt_file(path).map(|(handbook, report)| (handbook, Some(report)))
+    };
+    let report = load(&path, txt).unwrap().report.unwrap();
+    assert_eq!(report.dropped, 1);
+    assert_eq!(load(&path, unparsable).unwrap().report, Some(report));
+}
+
+#[test]
+fn test_txt_parse_report() {
+    use crate::search::txt::{parse_txt, TxtDiagnostic, TxtProblem};
+
+    let txt = "\
+1: Before any category
+// Weapons
+11101: Dull Blade
+1140x: Prototype Rancour
+no separator
+
+# Furniture
+363101: Wooden Chair
+// Characters
+10000002: Kamisato Ayaka
+";
+    let (handbook, report) = parse_txt(txt.as_bytes(), None).unwrap();
+    let entries: Vec<_> = handbook
+        .iter()
+        .map(|item| (item.id, item.category.clone()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (1, Category::Unknown),
+            (11101, Category::Weapons),
+            (0, Category::Weapons),
+            (363101, Category::Unknown),
+            (10000002, Category::Characters),
+        ]
+    );
+
+    let diagnostic = |line, problem| TxtDiagnostic { line, problem };
+    assert_eq!(
+        report.diagnostics,
+        vec![
+            diagnostic(1, TxtProblem::MissingCategory),
+            diagnostic(
+                4,
+                TxtProblem::InvalidId {
+                    id: "1140x".to_string()
+                }
+            ),
+            diagnostic(5, TxtProblem::MissingSeparator),
+            diagnostic(
+                7,
+                TxtProblem::UnknownCategory {
+                    category: "Furniture".to_string()
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        (report.entries, report.dropped, report.defaulted),
+        (5, 1, 3)
+    );
+    assert_eq!(
+        report.summary(),
+        "4 problem(s), 1 line(s) dropped, 3 entry(ies) defaulted, \
+         first at line 1: entry before any category"
+    );
+
+    let (_, report) = parse_txt("// Weapons\n11101: Dull Blade\n".as_bytes(), None).unwrap();
+    assert!(report.is_clean());
+}
+
+#[test]
+fn test_file_watcher() {
+    use crate::search::watch::FileWatcher;
+    use std::fs;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("gmhandbook.txt");
+    fs::write(&path, "// Weapons\n11101: Dull Blade\n").unwrap();
+
+    let (sender, changes) = mpsc::channel();
+    // A short settle time keeps the test fast, and the quiet periods checked
+    // below only need to be a few times longer.
+    let settle = Duration::from_millis(100);
+    let watcher =
+        FileWatcher::with_settle(&path, settle, move || sender.send(()).unwrap()).unwrap();
+    assert_eq!(watcher.path().file_name(), path.file_name());
+    // Returns as soon as a change is reported.
+    let changed = || changes.recv_timeout(Duration::from_secs(5)).is_ok();
+    let quiet = || changes.recv_timeout(settle * 5).is_err();
+
+    // Other files in the directory are ignored
+    fs::write(dir.path().join("gmhandbook.txt.cache"), "cache").unwrap();
+    assert!(quiet());
+
+    // Several writes in a row are reported once
+    fs::write(&path, "// Weapons\n").unwrap();
+    fs::write(
+        &path,
+        "// Weapons\n11101: Dull Blade\n11406: Prototype Rancour\n",
+    )
+    .unwrap();
+    assert!(changed());
+    assert!(quiet());
+
+    // Replacing the file through a rename is followed too
+    let replacement = dir.path().join("gmhandbook.txt.tmp");
+    fs::write(&replacement, "// Weapons\n").unwrap();
+    fs::rename(&replacement, &path).unwrap();
+    assert!(changed());
+
+    // Nothing is reported once the watcher is dropped
+    drop(watcher);
+    fs::write(&path, "// Weapons\n11101: Dull Blade\n").unwrap();
+    assert!(quiet());
+}
+
+#[test]
+fn test_txt_server_handbooks() {
+    use crate::search::txt::{language_from_file_name, parse_txt};
+    use crate::structure::handbook::Language;
+    use std::path::Path;
+
+    let entries = |handbook: &Gmhandbook| {
+        handbook
+            .iter()
+            .map(|item| (item.id, item.category.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let grasscutter = "\
+// Grasscutter 1.7.4 GM Handbook
+// Created Sat Jun 01 12:00:00 CST 2024
+
+// Commands
+account : Modify user accounts
+give : Gives an item to you or the specified player
+
+// Avatars
+10000002 : Kamisato Ayaka
+
+// Items
+11101 : Dull Blade
+76544 : Gladiator's Nostalgia
+104003 : Hero's Wit
+
+// Scenes
+3 : Teyvat : SCENE_WORLD
+
+// Monsters
+20010101 : Large Dendro Slime
+";
+    let (handbook, report) = parse_txt(grasscutter.as_bytes(), Some(Language::EN)).unwrap();
+    assert!(report.is_clean(), "{}", report.summary());
+    assert_eq!(
+        entries(&handbook),
+        vec![
+            (10000002, Category::Characters),
+            (11101, Category::Weapons),
+            (76544, Category::Artifacts),
+            (104003, Category::Materials),
+            (3, Category::Scenes),
+            (20010101, Category::Monsters),
+        ]
+    );
+    assert_eq!(handbook[4].gmhandbook_type, Some(Type::World));
+    match &handbook[0].name {
+        NameUnion::Description(names) => assert_eq!(names["EN"], "Kamisato Ayaka"),
+        NameUnion::String(_) => panic!("the name should be in English"),
+    }
+
+    let lunar_core = "\
+# Lunar Core 2.3.0 Handbook
+# Created 2024-06-01
+
+# Commands
+/give [item id] x(amount) lv(level). Gives an item to a player.
+
+# Avatars
+1001 : March 7th
+
+# Items
+23002 : Something Irreplaceable
+
+# Props (Spawnable)
+100 : Treasure Chest
+
+# NPC Monsters (Spawnable)
+1002010 : Frigid Prowler
+";
+    let (handbook, report) = parse_txt(lunar_core.as_bytes(), None).unwrap();
+    assert!(report.is_clean(), "{}", report.summary());
+    assert_eq!(
+        entries(&handbook),
+        vec![
+            (1001, Category::Avatars),
+            (23002, Category::Items),
+            (100, Category::PropsLC),
+            (1002010, Category::NPCMonstersLC),
+        ]
+    );
+    assert!(matches!(&handbook[0].name, NameUnion::String(name) if name == "March 7th"));
+
+    assert_eq!(
+        language_from_file_name(Path::new("GM Handbook/GM Handbook - CHS.txt")),
+        Some(Language::Chs)
+    );
+    assert_eq!(
+        language_from_file_name(Path::new("handbook_JP.txt")),
+        Some(Language::JP)
+    );
+    assert_eq!(language_from_file_name(Path::new("gmhandbook.txt")), None);
+    // Only uppercase codes are languages, not words ending a file name
+    assert_eq!(
+        language_from_file_name(Path::new("gm_handbook_id.txt")),
+        None
+    );
+    assert_eq!(language_from_file_name(Path::new("weapons_it.txt")), None);
+    assert_eq!(language_from_file_name(Path::new("handbook EN.txt")), None);
+}
+
+#[test]
+fn test_export() {
+    use crate::search::export::{write_export, ExportColumn, ExportFormat};
+    use crate::search::filter::SearchFilter;
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+    use crate::search::txt::parse_txt;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let request = SearchRequest {
+        search: "slime",
+        language: "EN",
+        filter: SearchFilter {
+            categories: Some(vec![Category::Monsters]),
+            ..Default::default()
+        },
+        descriptions: false,
+        offset: 0,
+        limit: None,
+    };
+    let results = search_handbook(&handbook, &index, &request).unwrap().items;
+    let export = |format, columns: &[ExportColumn]| {
+        let mut output = Vec::new();
+        write_export(&results, format, columns, &["EN", "JP"], &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert_eq!(
+        export(ExportFormat::Csv, &ExportColumn::DEFAULT),
+        "\
+id,name_EN,name_JP,category
+20011201,Dendro Slime,草スライム,Monsters
+20011202,Large Dendro Slime,,Monsters
+"
+    );
+
+    let txt = export(
+        ExportFormat::Txt,
+        &[ExportColumn::Id, ExportColumn::Name, ExportColumn::Rarity],
+    );
+    assert_eq!(
+        txt,
+        concat!(
+            "// Monsters\n",
+            "20011201 : Dendro Slime : 草スライム : \n",
+            "20011202 : Large Dendro Slime :  : \n",
+        )
+    );
+    let (reimported, report) = parse_txt(txt.as_bytes(), None).unwrap();
+    assert!(report.is_clean());
+    assert_eq!(
+        reimported
+            .iter()
+            .map(|item| (item.id, item.category.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (20011201, Category::Monsters),
+            (20011202, Category::Monsters)
+        ]
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&export(
+        ExportFormat::Json,
+        &[ExportColumn::Id, ExportColumn::Name, ExportColumn::Rarity],
+    ))
+    .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {"id": 20011201, "name": {"EN": "Dendro Slime", "JP": "草スライム"}, "rarity": null},
+            {"id": 20011202, "name": {"EN": "Large Dendro Slime"}, "rarity": null},
+        ])
+    );
+
+    assert_eq!(
+        ExportFormat::from_path(std::path::Path::new("slimes.CSV")),
+        Some(ExportFormat::Csv)
+    );
+}
+
+#[test]
+fn test_export_txt_languages() {
+    use crate::search::export::{
+        export_handbook, write_export, ExportColumn, ExportFormat, ExportOptions,
+    };
+    use crate::search::gi::SearchRequest;
+    use crate::search::index::HandbookIndex;
+
+    let handbook = sample_handbook();
+    let index = HandbookIndex::build(&handbook);
+    let request = SearchRequest {
+        search: "slime",
+        language: "EN",
+        ..Default::default()
+    };
+    let results = search_handbook(&handbook, &index, &request).unwrap().items;
+    let export = |languages: &[&str]| {
+        let mut output = Vec::new();
+        let columns = [ExportColumn::Id, ExportColumn::Name];
+        write_export(
+            &results,
+            ExportFormat::Txt,
+            &columns,
+            languages,
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    // The name comes from the first language that has one, and only its
+    // cell is left out of the other names
+    assert_eq!(
+        export(&["JP", "EN"]),
+        concat!(
+            "// Monsters\n",
+            "20011201 : 草スライム : Dendro Slime\n",
+            "20011202 : Large Dendro Slime : \n",
+        )
+    );
+    assert_eq!(export(&[]), "// Monsters\n20011201 : \n20011202 : \n");
+
+    let options = ExportOptions {
+        path: "slimes.txt".to_string(),
+        format: None,
+        columns: None,
+        languages: Some(Vec::new()),
+    };
+    assert!(export_handbook("slime", "EN", None, None, None, options).is_err());
+}
+
+#[test]
+fn test_export_star_rail() {
+    use crate::search::export::{write_export, ExportColumn, ExportFormat};
+    use crate::search::gi::{search_handbooks, SearchRequest, SearchSource};
+    use crate::search::index::HandbookIndex;
+    use crate::search::registry::Game;
+    use crate::search::txt::parse_txt;
+
+    let lunar_core = "\
+# Avatars
+1001 : March 7th
+
+# Light Cones
+23002 : Something Irreplaceable
+";
+    let (handbook, _) = parse_txt(lunar_core.as_bytes(), None).unwrap();
+    let index = HandbookIndex::build(&handbook);
+    let sources = [SearchSource {
+        handbook: &handbook,
+        index: &index,
+        name: None,
+        game: Some(Game::StarRail),
+    }];
+    let request = SearchRequest {
+        search: "",
+        language: "EN",
+        ..Default::default()
+    };
+    let results = search_handbooks(&sources, &request).unwrap().items;
+    let mut output = Vec::new();
+    write_export(
+        &results,
+        ExportFormat::Txt,
+        &ExportColumn::DEFAULT,
+        &["EN"],
+        &mut output,
+    )
+    .unwrap();
+    let txt = String::from_utf8(output).unwrap();
+    assert_eq!(
+        txt,
+        "# Avatars\n1001 : March 7th\n\n# Light Cones\n23002 : Something Irreplaceable\n"
+    );
+
+    // Exported handbooks are read back with the same categories
+    let (reimported, report) = parse_txt(txt.as_bytes(), None).unwrap();
+    assert!(report.is_clean(), "{}", report.summary());
+    assert_eq!(
+        reimported
+            .iter()
+            .map(|item| (item.id, item.category.clone()))
+            .collect::<Vec<_>>(),
+        vec![(1001, Category::Avatars), (23002, Category::LightCones)]
+    );
+}
+
+#[test]
+fn test_handbook_diff() {
+    use crate::search::diff::{HandbookDiff, NameChange};
+    use crate::search::export::ExportFormat;
+
+    let old = sample_handbook();
+    let mut new = sample_handbook();
+    // Renamed in English only
+    new[1] = element(
+        11501,
+        Category::Weapons,
+        &[
+            ("EN", "Aquila Favonia (Old)"),
+            ("JP", "風鷹剣"),
+            ("VI", "Phong Ưng Kiếm"),
+        ],
+    );
+    // Moved to another category
+    new[6].category = Category::Items;
+    // Removed, and added
+    new.remove(9);
+    new.push(element(
+        11513,
+        Category::Weapons,
+        &[("EN", "Splendor of Tranquil Waters")],
+    ));
+
+    let diff = HandbookDiff::new(&old, &new);
+    assert_eq!(
+        diff.added.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+        vec![11513]
+    );
+    assert_eq!(
+        diff.removed
+            .iter()
+            .map(|entry| (entry.id, entry.category.clone()))
+            .collect::<Vec<_>>(),
+        vec![(3, Category::Scenes)]
+    );
+    assert_eq!(diff.renamed.len(), 1);
+    // The new VI name is not a rename, and the CHS name is gone
+    assert_eq!(
+        diff.renamed[0].changes,
+        vec![NameChange {
+            language: "EN".to_string(),
+            old: "Aquila Favonia".to_string(),
+            new: "Aquila Favonia (Old)".to_string(),
+        }]
+    );
+    assert_eq!(diff.recategorized.len(), 1);
+    assert_eq!(
+        (
+            diff.recategorized[0].id,
+            &diff.recategorized[0].old_category,
+            &diff.recategorized[0].new_category
+        ),
+        (101, &Category::Materials, &Category::Items)
+    );
+    assert_eq!(diff.unchanged, 7);
+
+    let report = |format| {
+        let mut output = Vec::new();
+        diff.write_report(format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    assert_eq!(
+        report(ExportFormat::Txt),
+        "\
+# Added (1)
+11513 [Weapons] EN: Splendor of Tranquil Waters
+
+# Removed (1)
+3 [Scenes] EN: Teyvat
+
+# Renamed (1)
+11501 [Weapons] EN: Aquila Favonia -> Aquila Favonia (Old)
+
+# Recategorized (1)
+101 [Materials -> Items]
+"
+    );
+    assert_eq!(
+        report(ExportFormat::Csv),
+        "\
+change,id,category,old_category,language,old_name,new_name
+added,11513,Weapons,,EN,,Splendor of Tranquil Waters
+removed,3,Scenes,,EN,Teyvat,
+renamed,11501,Weapons,,EN,Aquila Favonia,Aquila Favonia (Old)
+recategorized,101,Items,Materials,,,
+"
+    );
+    let json: serde_json::Value = serde_json::from_str(&report(ExportFormat::Json)).unwrap();
+    assert_eq!(json["recategorized"][0]["new_category"], "Items");
+}
+
+#[test]
+fn test_suggest() {
+    use crate::search::gi::SearchSource;
+    use crate::search::index::HandbookIndex;
+    use crate::search::suggest::{suggest_names, SuggestRequest};
+
+    let mut handbook = sample_handbook();
+    handbook.push(GmhandbookElement {
+        rarity: Some(3),
+        ..element(11301, Category::Weapons, &[("EN", "Skyrider Sword")])
+    });
+    let index = HandbookIndex::build(&handbook);
+    let sources = [SearchSource {
+        handbook: &handbook,
+        index: &index,
+        name: None,
+        game: None,
+    }];
+    let suggest = |prefix: &str, language: &str, category: Option<Category>, limit: usize| {
+        let request = SuggestRequest {
+            prefix,
+            language,
+            category,
+            limit,
+        };
+        suggest_names(&sources, &request)
+            .unwrap()
+            .into_iter()
+            .map(|suggestion| (suggestion.id, suggestion.name))
+            .collect::<Vec<_>>()
+    };
+    let ids = |suggestions: Vec<(i64, String)>| {
+        suggestions
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>()
+    };
+
+    // Higher rarity first, then shorter names
+    assert_eq!(
+        ids(suggest("sky", "EN", None, 10)),
+        vec![15501, 11502, 11301]
+    );
+    assert_eq!(ids(suggest("SKY", "EN", None, 2)), vec![15501, 11502]);
+    // Names starting with the prefix come before names with a word starting with it
+    assert_eq!(
+        ids(suggest("d", "EN", None, 10)),
+        vec![13401, 20011201, 20011202]
+    );
+    assert_eq!(
+        ids(suggest("slime", "EN", None, 10)),
+        vec![20011201, 20011202]
+    );
+    assert_eq!(
+        ids(suggest("d", "EN", Some(Category::Monsters), 10)),
+        vec![20011201, 20011202]
+    );
+    // Names are returned in the language they matched, equal ranks by ID
+    assert_eq!(
+        suggest("天空", "auto", None, 10),
+        vec![
+            (11502, "天空の刃".to_string()),
+            (15501, "天空の翼".to_string())
+        ]
+    );
+    // Every word but the last is matched whole, like `find` does
+    assert_eq!(ids(suggest("amos b", "EN", None, 10)), vec![15502]);
+    assert_eq!(ids(suggest("Amos'  BO", "EN", None, 10)), vec![15502]);
+    assert!(suggest("ward b", "EN", None, 10).is_empty());
+    assert_eq!(ids(suggest("large d", "EN", None, 10)), vec![20011202]);
+    assert_eq!(suggest("", "EN", None, 10), vec![]);
+    assert_eq!(suggest("sky", "EN", None, 0), vec![]);
+}
+
+#[test]
+fn test_suggest_star_rail_avatars() {
+    use crate::search::gi::SearchSource;
+    use crate::search::index::HandbookIndex;
+    use crate::search::suggest::{suggest_names, SuggestRequest};
+    use crate::search::txt::parse_txt;
+
+    let lunar_core = "\
+# Avatars
+1001 : March 7th
+1002 : Dan Heng
+
+# Items
+23002 : Something Irreplaceable
+";
+    let (handbook, _) = parse_txt(lunar_core.as_bytes(), None).unwrap();
+    let index = HandbookIndex::build(&handbook);
+    let sources = [SearchSource {
+        handbook: &handbook,
+        index: &index,
+        name: None,
+        game: None,
+    }];
+    let request = SuggestRequest {
+        prefix: "ma",
+        language: "EN",
+        category: Some(Category::Characters),
+        limit: 10,
+    };
+    // Star Rail avatars are listed as characters, as `find` filters them
+    let suggestions = suggest_names(&sources, &request).unwrap();
+    assert_eq!(
+        suggestions
+            .iter()
+            .map(|suggestion| (suggestion.id, suggestion.category.clone()))
+            .collect::<Vec<_>>(),
+        vec![(1001, Category::Avatars)]
+    );
+}
+
+#[test]
+fn test_item_summary() {
+    use crate::search::filter::SearchFilter;
+    use crate::search::gi::{summarize_handbooks, SearchRequest, SearchSource};
+    use crate::search::index::HandbookIndex;
+    use crate::search::item::find_item;
+    use std::collections::HashMap;
+
+    let mut handbook = sample_handbook();
+    handbook[2].description = Some(HashMap::from([(
+        "EN".to_string(),
+        "A sword that soars.".to_string(),
+    )]));
+    let index = HandbookIndex::build(&handbook);
+    let sources = [SearchSource {
+        handbook: &handbook,
+        index: &index,
+        name: Some("gi"),
+        game: None,
+    }];
+    let summarize = |search: &str, language: &str| {
+        let request = SearchRequest {
+            search,
+            language,
+            limit: Some(1),
+            ..Default::default()
+        };
+        summarize_handbooks(&sources, &request)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|result| (result.item.id, result.item.name))
+            .collect::<Vec<_>>()
+    };
+
+    // Names are summarized in the language they matched
+    assert_eq!(
+        summarize("天空の刃", "auto"),
+        vec![(11502, "天空の刃".to_string())]
+    );
+    assert_eq!(
+        summarize("skyward blade", "all"),
+        vec![(11502, "Skyward Blade".to_string())]
+    );
+    // or in the searched language for ID matches
+    assert_eq!(
+        summarize("11502", "RU"),
+        vec![(11502, "Небесный меч".to_string())]
+    );
+
+    let request = SearchRequest {
+        search: "skyward blade",
+        language: "EN",
+        ..Default::default()
+    };
+    let response = summarize_handbooks(&sources, &request).unwrap();
+    let json = serde_json::to_value(&response.items[0]).unwrap();
+    assert_eq!(json["name"], "Skyward Blade");
+    assert_eq!(json["category"], "Weapons");
+    assert_eq!(json["handbook"], "gi");
+    assert!(json.get("description").is_none());
+    assert!(json.get("commands").is_none());
+
+    // The full entry is looked up by category and ID
+    let item = find_item(&sources, &Category::Weapons, 11502).unwrap();
+    assert!(item.item.description.is_some());
+    assert_eq!(item.handbook.as_deref(), Some("gi"));
+    assert_eq!(
+        find_item(&sources, &Category::Materials, 11502)
+            .err()
+            .map(|e| e.to_string()),
+        Some("No Materials entry with ID 11502".to_string())
+    );
+
+    // Star Rail avatars are found as characters, as `find` lists them
+    let avatars = vec![element(1001, Category::Avatars, &[("EN", "March 7th")])];
+    let index = HandbookIndex::build(&avatars);
+    let sources = [SearchSource {
+        handbook: &avatars,
+        index: &index,
+        name: Some("sr"),
+        game: None,
+    }];
+    let item = find_item(&sources, &Category::Characters, 1001).unwrap();
+    assert_eq!(item.item.category, Category::Avatars);
+}
+
+#[test]
+fn test_resolve_ids() {
+    use crate::search::gi::SearchSource;
+    use crate::search::index::HandbookIndex;
+    use crate::search::resolve::{resolve, IdQuery, Resolution};
+
+    let mut handbook = sample_handbook();
+    handbook.push(element(
+        11502,
+        Category::Achievements,
+        &[("EN", "Sky High")],
+    ));
+    let index = HandbookIndex::build(&handbook);
+    let sources = [SearchSource {
+        handbook: &handbook,
+        index: &index,
+        name: None,
+        game: None,
+    }];
+    let query = |id: i64, category: Option<Category>| IdQuery { id, category };
+    let resolved = resolve(
+        &sources,
+        &[
+            query(15502, None),
+            query(11502, None),
+            query(11502, Some(Category::Achievements)),
+            query(99999, None),
+        ],
+        "JP",
+    )
+    .unwrap();
+
+    let Resolution::Found(found) = &resolved[0].resolution else {
+        panic!("15502 was not found");
+    };
+    assert_eq!(found.item.name, "アモスの弓");
+    // IDs used in several categories are ambiguous, unless restricted to one
+    let Resolution::Ambiguous { candidates } = &resolved[1].resolution else {
+        panic!("11502 is not ambiguous");
+    };
+    assert_eq!(
+        candidates
+            .iter()
+            .map(|candidate| (
+                candidate.item.category.clone(),
+                candidate.item.name.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Category::Weapons, "天空の刃"),
+            // Falls back to another language
+            (Category::Achievements, "Sky High"),
+        ]
+    );
+    let Resolution::Found(found) = &resolved[2].resolution else {
+        panic!("11502 was not found in Achievements");
+    };
+    assert_eq!(found.item.name, "Sky High");
+    assert_eq!(resolved[3].resolution, Resolution::Unknown);
+
+    let json = serde_json::to_value(&resolved[3]).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"id": 99999, "category": null, "status": "unknown"})
+    );
+    assert!(resolve(&sources, &[], "xx").is_err());
+
+    // Star Rail avatars resolve as characters, as `find` lists them
+    let avatars = vec![element(1001, Category::Avatars, &[("EN", "March 7th")])];
+    let index = HandbookIndex::build(&avatars);
+    let sources = [SearchSource {
+        handbook: &avatars,
+        index: &index,
+        name: None,
+        game: None,
+    }];
+    let resolved = resolve(&sources, &[query(1001, Some(Category::Characters))], "EN").unwrap();
+    let Resolution::Found(found) = &resolved[0].resolution else {
+        panic!("1001 was not found in Characters");
+    };
+    assert_eq!(found.item.name, "March 7th");
+}
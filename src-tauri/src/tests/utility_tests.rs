@@ -61,6 +61,60 @@ fn test_read_text_map() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_read_text_map_filtered() {
+    use crate::utility::read_text_map_filtered;
+    use std::collections::HashSet;
+
+    let temp_dir = TempDir::new().unwrap();
+    let text_map_path = temp_dir.path().join("TextMap");
+    fs::create_dir_all(&text_map_path).unwrap();
+    let path = text_map_path.to_str().unwrap();
+    let hashes =
+        |keys: &[&str]| -> HashSet<String> { keys.iter().map(|key| key.to_string()).collect() };
+
+    // Values of unwanted hashes are skipped whatever their type
+    let text_map_content = json!({
+        "key1": "value1",
+        "key2": "value2",
+        "key3": {"nested": [1, 2, 3]},
+        "key4": 4
+    });
+    fs::write(
+        text_map_path.join("TextMapEN.json"),
+        text_map_content.to_string(),
+    )
+    .unwrap();
+    let text_map = read_text_map_filtered(path, "EN", &hashes(&["key1", "missing"])).unwrap();
+    assert_eq!(text_map.len(), 1);
+    assert_eq!(text_map.get("key1"), Some(&"value1".to_string()));
+    // Hashes missing from the TextMap are left out
+    assert_eq!(text_map.get("missing"), None);
+    assert!(read_text_map_filtered(path, "EN", &hashes(&[]))
+        .unwrap()
+        .is_empty());
+
+    // A wanted value that is not a string is an error
+    let result = read_text_map_filtered(path, "EN", &hashes(&["key4"]));
+    assert!(matches!(result, Err(TextMapError::JsonError(_))));
+
+    // Same errors as reading the whole TextMap
+    let result = read_text_map_filtered(path, "FR", &hashes(&["key1"]));
+    assert!(matches!(result, Err(TextMapError::IoError(_))));
+    fs::write(
+        text_map_path.join("TextMapInvalid.json"),
+        "invalid json content",
+    )
+    .unwrap();
+    assert!(read_text_map_filtered(path, "Invalid", &hashes(&["key1"])).is_err());
+    fs::write(
+        text_map_path.join("TextMapTrailing.json"),
+        r#"{"key1": "value1"} {}"#,
+    )
+    .unwrap();
+    assert!(read_text_map_filtered(path, "Trailing", &hashes(&["key1"])).is_err());
+}
+
 #[derive(Debug, Deserialize, PartialEq)]
 struct TestData {
     id: u32,
@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fmt, fs, io, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    io::{self, BufReader},
+    path::Path,
+};
 
 use std::fs::File;
 use std::sync::Mutex;
 
-use serde::de::{DeserializeOwned, Error};
+use serde::de::{DeserializeOwned, DeserializeSeed, Error, IgnoredAny, MapAccess, Visitor};
 
 pub type TextMap = HashMap<String, String>;
 
@@ -149,6 +154,10 @@ where
 /// * The specified text map file does not exist.
 /// * There's an I/O error while reading the file.
 /// * The JSON data cannot be parsed into a `TextMap` struct.
+///
+/// Only used by tests: generating a handbook streams the TextMaps with
+/// [`read_text_map_filtered`] instead, keeping only the texts it needs.
+#[cfg(test)]
 pub(crate) fn read_text_map(path: &str, lang: &str) -> Result<TextMap, TextMapError> {
     let path = Path::new(&path).join(format!("TextMap{}.json", lang.to_uppercase()));
     if !path.exists() {
@@ -161,3 +170,82 @@ pub(crate) fn read_text_map(path: &str, lang: &str) -> Result<TextMap, TextMapEr
     let data: TextMap = serde_json::from_str(&contents)?;
     Ok(data)
 }
+
+/// Keeps the entries of a TextMap whose hash is wanted, skipping the others
+/// without allocating their text.
+struct FilteredTextMap<'a>(&'a HashSet<String>);
+
+impl<'de> DeserializeSeed<'de> for FilteredTextMap<'_> {
+    type Value = TextMap;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<TextMap, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FilteredTextMap<'_> {
+    type Value = TextMap;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map of text hashes to texts")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<TextMap, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut text_map = TextMap::with_capacity(self.0.len());
+        while let Some(hash) = map.next_key::<String>()? {
+            if self.0.contains(&hash) {
+                text_map.insert(hash, map.next_value()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(text_map)
+    }
+}
+
+/// Streams a text map JSON file, keeping only the texts of `hashes`.
+///
+/// A whole TextMap takes several hundred megabytes once loaded, while a
+/// handbook only needs the names and descriptions of the Excel rows it was
+/// generated from.
+///
+/// # Arguments
+///
+/// * `path` - A string representing the base path to the game data.
+/// * `lang` - The language code for the desired text map.
+/// * `hashes` - The text hashes to keep.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `TextMap` if successful, or a `TextMapError` if an error occurs.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The specified text map file does not exist.
+/// * There's an I/O error while reading the file.
+/// * The JSON data cannot be parsed into a `TextMap` struct.
+pub(crate) fn read_text_map_filtered(
+    path: &str,
+    lang: &str,
+    hashes: &HashSet<String>,
+) -> Result<TextMap, TextMapError> {
+    let path = Path::new(&path).join(format!("TextMap{}.json", lang.to_uppercase()));
+    if !path.exists() {
+        return Err(TextMapError::IoError(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("TextMap file not found at `{}`", path.display()),
+        )));
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let data = FilteredTextMap(hashes).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(data)
+}
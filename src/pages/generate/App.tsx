@@ -37,7 +37,10 @@ const App: React.FC = (): JSX.Element => {
 	}, [])
 
 	const [languages, setLanguages] = useState<string[]>([])
-	const selections = ['Characters', 'Materials', 'Weapons', 'Artifacts', 'Quests', 'Dungeons', 'Scenes', 'Monsters']
+	const selections =
+		gameType === 'star-rail'
+			? ['Avatars', 'Light Cones', 'Relics', 'Items', 'Monsters']
+			: ['Characters', 'Materials', 'Weapons', 'Artifacts', 'Quests', 'Dungeons', 'Scenes', 'Monsters']
 
 	const generate = useCallback(async () => {
 		setLoading(true)
@@ -214,6 +217,7 @@ const App: React.FC = (): JSX.Element => {
 						<div>
 							<Label className='mb-2 block'>List To Include</Label>
 							<MultiSelect
+								key={gameType}
 								options={selections.map((selection) => ({
 									label: selection,
 									value: selection,
@@ -228,7 +232,12 @@ const App: React.FC = (): JSX.Element => {
 						<div>
 							<div>
 								<Label className='mb-2 block'>Type of Game</Label>
-								<Select onValueChange={(value) => setGameType(value)}>
+								<Select
+									onValueChange={(value) => {
+										setGameType(value)
+										setSelectedSelections([])
+									}}
+								>
 									<SelectTrigger>
 										<SelectValue placeholder='Select a Game' />
 									</SelectTrigger>
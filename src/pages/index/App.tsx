@@ -1,7 +1,7 @@
 import ElaXanAPI from '@/api/elaxanApi'
 import YuukiPS from '@/api/yuukips'
 import { Button } from '@/components/ui/button'
-import type { Description, GmhandbookGI } from '@/types/gm'
+import type { Description, GmhandbookGI, SearchResponse } from '@/types/gm'
 import { invoke, isTauri } from '@tauri-apps/api/core'
 import { error } from '@tauri-apps/plugin-log'
 import { useEffect, useMemo, useState } from 'react'
@@ -59,11 +59,13 @@ function App() {
 		try {
 			let response: GmhandbookGI[] = []
 			if (isTauri()) {
-				response = await invoke<GmhandbookGI[]>('find', {
+				const result = await invoke<SearchResponse>('find', {
 					search: state.searchTerm.split(',').map((e) => e.trim()),
 					language: currentLanguage,
 					limit: state.currentLimit,
+					filter: state.selectedCategory.length ? { categories: state.selectedCategory } : undefined,
 				})
+				response = result.items
 			} else {
 				response = await ElaXanAPI.getHandbook(state.baseURL, 'gi', {
 					search: state.searchTerm.split(',').map((e) => e.trim()),
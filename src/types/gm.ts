@@ -29,6 +29,159 @@ export interface GmhandbookGI {
 	command?: Commands;
 	icon?: string;
 	type?: Type;
+	score?: number;
+	matched_language?: string | null;
+	matched_field?: 'name' | 'description' | 'id' | null;
+	snippet?: Snippet | null;
+	handbook?: string;
+	game?: Game;
+}
+
+export type Game = 'Genshin Impact' | 'Star Rail'
+
+export interface HandbookInfo {
+	name: string;
+	game: Game;
+	path: string;
+	entries: number;
+	report?: ParseReport;
+}
+
+export type TxtProblem =
+	| { kind: "missing_separator" }
+	| { kind: "invalid_id"; id: string }
+	| { kind: "unknown_category"; category: string }
+	| { kind: "missing_category" };
+
+export interface ParseReport {
+	entries: number;
+	dropped: number;
+	defaulted: number;
+	diagnostics: { line: number; problem: TxtProblem }[];
+}
+
+/** Payload of the `handbook-reloaded` event. */
+export interface HandbookReload {
+	path: string;
+	entries: number | null;
+	report?: ParseReport;
+	error: string | null;
+}
+
+export type ExportFormat = "csv" | "txt" | "json";
+
+export type ExportColumn =
+	| "id"
+	| "name"
+	| "description"
+	| "category"
+	| "rarity"
+	| "handbook"
+	| "game"
+	| "score";
+
+export interface ExportOptions {
+	path: string;
+	format?: ExportFormat;
+	columns?: ExportColumn[];
+	languages?: string[];
+}
+
+export interface ExportSummary {
+	path: string;
+	format: ExportFormat;
+	entries: number;
+}
+
+export interface DiffEntry {
+	id: number;
+	category: string;
+	names: Record<string, string>;
+}
+
+export interface NameChange {
+	language: string;
+	old: string;
+	new: string;
+}
+
+export interface HandbookDiff {
+	added: DiffEntry[];
+	removed: DiffEntry[];
+	renamed: { id: number; category: string; changes: NameChange[] }[];
+	recategorized: {
+		id: number;
+		old_category: string;
+		new_category: string;
+		changes: NameChange[];
+	}[];
+	unchanged: number;
+}
+
+export interface Suggestion {
+	id: number;
+	name: string;
+	category: string;
+	rarity: number | null;
+	handbook?: string;
+}
+
+export interface IdQuery {
+	id: number;
+	category?: Category;
+}
+
+export interface IdCandidate {
+	id: number;
+	name: string;
+	category: Category;
+	rarity: number | null;
+	image: string | null;
+	handbook?: string;
+}
+
+export type ResolvedId = { id: number; category: Category | null } & (
+	| ({ status: "found" } & IdCandidate)
+	| { status: "ambiguous"; candidates: IdCandidate[] }
+	| { status: "unknown" }
+);
+
+export interface Snippet {
+	text: string;
+	/** Ranges of `text` in UTF-16 code units, so they can be passed to `slice`. */
+	highlights: { start: number; end: number }[];
+}
+
+export interface SearchFilter {
+	categories?: string[];
+	rarity?: { min?: number; max?: number };
+	types?: Type[];
+}
+
+export interface ItemSummary {
+	id: number;
+	name: string;
+	category: Category;
+	rarity: number | null;
+	image: string | null;
+	score: number;
+	matched_language: string | null;
+	matched_field: 'name' | 'description' | 'id' | null;
+	snippet: Snippet | null;
+	handbook?: string;
+	game?: Game;
+}
+
+export interface SearchResponse<T = GmhandbookGI> {
+	items: T[];
+	total: number;
+	offset: number;
+	limit?: number | null;
+	next_offset?: number | null;
+	query: string;
+	filter: SearchFilter;
+	languages: string[];
+	handbooks: string[];
 }
 
 export enum Category {